@@ -0,0 +1,593 @@
+//! Accepted answers of a dictionary cell and grading of the typed answers
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{fill, text, Msg};
+use crate::words::{check_spelling_simple, umlaut_normalize};
+
+/// Separators of the alternative answers in a cell: `Bank, Sitzbank`, `идти/ходить`
+const SEPARATORS: [char; 3] = [',', '/', ';'];
+
+/// Each optional part doubles the number of accepted answers, the rest is taken literally
+const MAX_OPTIONAL_PARTS: usize = 4;
+
+const ARTICLES: [&str; 3] = ["der", "die", "das"];
+
+/// Articles in all cases, their capitalization is not a mistake: "Der Hund", "Dem alten"
+const DECLINED_ARTICLES: [&str; 12] = [
+    "der", "die", "das", "den", "dem", "des", "ein", "eine", "einen", "einem", "einer", "eines",
+];
+
+/// Small slip in an answer which is otherwise correct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mistake {
+    /// Wrong letter, a missing or an extra one
+    Typo,
+    /// Vowel without umlaut: `Ubung` for `Übung`
+    MissingUmlaut,
+    /// Noun in lower case or an article in upper case in the middle
+    Capitalization,
+    /// Wrong article with the correct noun
+    Article,
+}
+
+impl Display for Mistake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::Typo => Msg::MistakeTypo,
+            Self::MissingUmlaut => Msg::MistakeUmlaut,
+            Self::Capitalization => Msg::MistakeCapitalization,
+            Self::Article => Msg::MistakeArticle,
+        };
+        write!(f, "{}", text(msg))
+    }
+}
+
+/// Outcome of an exercise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    /// Answer is one of the accepted ones
+    Correct,
+    /// Answer with a small slip
+    NearMiss(Mistake),
+    /// Answer is not accepted
+    Wrong,
+}
+
+impl Verdict {
+    /// Only an exact answer is correct, a near-miss is not
+    pub fn is_correct(self) -> bool {
+        self == Verdict::Correct
+    }
+
+    /// Rank for choosing the best verdict among the alternatives
+    fn rank(self) -> u8 {
+        match self {
+            Verdict::Correct => 2,
+            Verdict::NearMiss(_) => 1,
+            Verdict::Wrong => 0,
+        }
+    }
+}
+
+impl From<bool> for Verdict {
+    fn from(correct: bool) -> Self {
+        if correct {
+            Verdict::Correct
+        } else {
+            Verdict::Wrong
+        }
+    }
+}
+
+/// Answers accepted for a dictionary cell: alternatives separated by `,` `/` or `;`
+/// with optional parts in parentheses, e.g. `(sich) erinnern`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerSpec {
+    alternatives: Vec<String>,
+    accepted: Vec<String>,
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Lowercase spelling with umlauts typed as ae, oe, ue
+fn fold(s: &str) -> String {
+    umlaut_normalize(&s.to_lowercase())
+}
+
+/// Lowercase Cyrillic spelling with ё typed as е
+fn fold_cyrillic(s: &str) -> String {
+    s.to_lowercase().replace('ё', "е")
+}
+
+/// Lowercase spelling with umlauts replaced by the plain vowels
+fn strip_umlauts(s: &str) -> String {
+    s.to_lowercase()
+        .replace('ä', "a")
+        .replace('ö', "o")
+        .replace('ü', "u")
+}
+
+fn capitals(s: &str) -> Vec<bool> {
+    s.split_whitespace()
+        .map(|w| {
+            w.starts_with(char::is_uppercase)
+                && !DECLINED_ARTICLES.contains(&w.to_lowercase().as_str())
+        })
+        .collect()
+}
+
+/// Number of typos allowed in the answer depending on the length of the word
+fn max_typos(expected: &str) -> usize {
+    match expected.chars().count() {
+        0..=3 => 0,
+        4..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Table of edit distances between the prefixes of the strings,
+/// optionally counting a swap of two adjacent letters as a single edit
+fn edit_table(a: &[char], b: &[char], swaps: bool) -> Vec<Vec<usize>> {
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if swaps && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d
+}
+
+fn typo_distance(a: &str, b: &str) -> usize {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    edit_table(&a, &b, true)[a.len()][b.len()]
+}
+
+/// Compare the answer with a single accepted spelling
+fn classify(answer: &str, expected: &str) -> Verdict {
+    if fold(answer) == fold(expected) {
+        return if capitals(answer) == capitals(expected) {
+            Verdict::Correct
+        } else {
+            Verdict::NearMiss(Mistake::Capitalization)
+        };
+    }
+    if strip_umlauts(answer) == strip_umlauts(expected) {
+        return Verdict::NearMiss(Mistake::MissingUmlaut);
+    }
+    if let (Some((article, noun)), Some((expected_article, expected_noun))) =
+        (answer.split_once(' '), expected.split_once(' '))
+    {
+        let article = article.to_lowercase();
+        if ARTICLES.contains(&article.as_str())
+            && ARTICLES.contains(&expected_article)
+            && fold(noun) == fold(expected_noun)
+        {
+            return Verdict::NearMiss(Mistake::Article);
+        }
+    }
+    if typo_distance(&fold(answer), &fold(expected)) <= max_typos(expected) {
+        return Verdict::NearMiss(Mistake::Typo);
+    }
+    Verdict::Wrong
+}
+
+/// Letter of the answer aligned with the expected spelling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    /// Same letter in the answer and the expected spelling
+    Same(char),
+    /// Letter of the answer and the expected one in its place
+    Replace(char, char),
+    /// Letter of the answer which is not expected
+    Extra(char),
+    /// Expected letter missing in the answer
+    Missing(char),
+}
+
+/// Align the answer with the expected spelling with the fewest edits
+pub fn diff_ops(answer: &str, expected: &str) -> Vec<DiffOp> {
+    let a: Vec<_> = answer.chars().collect();
+    let b: Vec<_> = expected.chars().collect();
+    let d = edit_table(&a, &b, false);
+
+    // walk back from the end, collecting the edits in reverse
+    let mut ops = vec![];
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]) {
+            if a[i - 1] == b[j - 1] {
+                ops.push(DiffOp::Same(a[i - 1]));
+            } else {
+                ops.push(DiffOp::Replace(a[i - 1], b[j - 1]));
+            }
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            ops.push(DiffOp::Extra(a[i - 1]));
+            i -= 1;
+        } else {
+            ops.push(DiffOp::Missing(b[j - 1]));
+            j -= 1;
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// Answer and expected spelling aligned by the edit operations, without highlighting
+pub fn diff_sides(ops: &[DiffOp]) -> (String, String) {
+    let mut ans = String::new();
+    let mut exp = String::new();
+    for op in ops {
+        match *op {
+            DiffOp::Same(c) => {
+                ans.push(c);
+                exp.push(c);
+            }
+            DiffOp::Replace(a, b) => {
+                ans.push(a);
+                exp.push(b);
+            }
+            DiffOp::Extra(a) => ans.push(a),
+            DiffOp::Missing(b) => exp.push(b),
+        }
+    }
+    (ans, exp)
+}
+
+/// Split the cell on the separators outside of parentheses
+fn split_alternatives(s: &str) -> Vec<String> {
+    let mut alternatives = vec![];
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && SEPARATORS.contains(&c) => {
+                alternatives.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    alternatives.push(current);
+    alternatives
+}
+
+/// All spellings of the alternative with and without the optional parts,
+/// the one with all parts comes first
+fn expand_optional(alternative: &str) -> Vec<String> {
+    let mut fixed = vec![String::new()];
+    let mut optional = vec![];
+    let mut rest = alternative;
+    while let Some(start) = rest.find('(') {
+        let Some(len) = rest[start..].find(')') else {
+            break;
+        };
+        fixed.last_mut().unwrap().push_str(&rest[..start]);
+        optional.push(&rest[start + 1..start + len]);
+        fixed.push(String::new());
+        rest = &rest[start + len + 1..];
+    }
+    fixed.last_mut().unwrap().push_str(rest);
+    if optional.is_empty() || optional.len() > MAX_OPTIONAL_PARTS {
+        return vec![normalize(alternative)];
+    }
+
+    let mut expanded = vec![];
+    for mask in 0..1usize << optional.len() {
+        let mut s = fixed[0].clone();
+        for (i, part) in optional.iter().enumerate() {
+            // bit set means the part is dropped, so mask 0 keeps all of them
+            if mask & (1 << i) == 0 {
+                s.push_str(part);
+            }
+            s.push_str(&fixed[i + 1]);
+        }
+        let s = normalize(&s);
+        if !s.is_empty() && !expanded.contains(&s) {
+            expanded.push(s);
+        }
+    }
+    expanded
+}
+
+impl AnswerSpec {
+    /// Parse the cell, the parts which are not understood are taken literally
+    pub fn parse(s: &str) -> Self {
+        Self::from_alternatives(split_alternatives(s))
+    }
+
+    /// Parse the cell reporting the parts which would be taken literally
+    pub fn parse_strict(s: &str) -> Result<Self, String> {
+        let mut depth = 0usize;
+        for c in s.chars() {
+            match c {
+                '(' if depth > 0 => {
+                    return Err(fill(Msg::NestedParentheses, &[&format!("{:?}", s)]))
+                }
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    return Err(fill(Msg::UnbalancedParentheses, &[&format!("{:?}", s)]))
+                }
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth > 0 {
+            return Err(fill(Msg::UnbalancedParentheses, &[&format!("{:?}", s)]));
+        }
+        let alternatives = split_alternatives(s);
+        if alternatives.len() > 1 && alternatives.iter().any(|a| a.trim().is_empty()) {
+            return Err(fill(Msg::EmptyAlternative, &[&format!("{:?}", s)]));
+        }
+        for alternative in &alternatives {
+            if alternative.matches('(').count() > MAX_OPTIONAL_PARTS {
+                return Err(fill(
+                    Msg::TooManyOptionalParts,
+                    &[&MAX_OPTIONAL_PARTS, &format!("{:?}", alternative)],
+                ));
+            }
+            let mut optional = alternative.split('(').skip(1);
+            if optional.any(|part| part.split(')').next().unwrap_or_default().trim().is_empty()) {
+                return Err(fill(
+                    Msg::EmptyOptionalPart,
+                    &[&format!("{:?}", alternative)],
+                ));
+            }
+        }
+        Ok(Self::from_alternatives(alternatives))
+    }
+
+    /// Accepted answers of the alternatives, the empty ones are dropped
+    pub fn from_alternatives(alternatives: Vec<String>) -> Self {
+        let alternatives: Vec<_> = alternatives
+            .iter()
+            .map(|a| normalize(a))
+            .filter(|a| !a.is_empty())
+            .collect();
+        let accepted = alternatives
+            .iter()
+            .flat_map(|a| expand_optional(a))
+            .collect();
+        Self {
+            alternatives,
+            accepted,
+        }
+    }
+
+    /// Cell without any alternative
+    pub fn is_empty(&self) -> bool {
+        self.alternatives.is_empty()
+    }
+
+    /// Alternatives as written in the cell, with the optional parts in parentheses
+    pub fn alternatives(&self) -> &[String] {
+        &self.alternatives
+    }
+
+    /// First alternative with all optional parts
+    pub fn primary(&self) -> &str {
+        self.accepted
+            .first()
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Answer is one of the accepted ones, ignoring the case and the umlaut spelling
+    pub fn matches(&self, answer: &str) -> bool {
+        let answer = normalize(answer);
+        self.accepted
+            .iter()
+            .any(|a| check_spelling_simple(&answer, a))
+    }
+
+    /// Grade the translation typed in Russian like [`AnswerSpec::check`], ignoring the case
+    /// and not distinguishing ё and е
+    pub fn check_translation(&self, answer: &str) -> (Verdict, &str) {
+        let answer = fold_cyrillic(&normalize(answer));
+        let mut best = (Verdict::Wrong, self.primary());
+        for expected in &self.accepted {
+            let verdict = classify(&answer, &fold_cyrillic(expected));
+            if verdict.rank() > best.0.rank() {
+                best = (verdict, expected);
+            }
+        }
+        best
+    }
+
+    /// Grade the answer against the closest accepted spelling, which is returned as well
+    pub fn check(&self, answer: &str) -> (Verdict, &str) {
+        let answer = normalize(answer);
+        let mut best = (Verdict::Wrong, self.primary());
+        for expected in &self.accepted {
+            let verdict = classify(&answer, expected);
+            if verdict.rank() > best.0.rank() {
+                best = (verdict, expected);
+            }
+        }
+        best
+    }
+
+    /// Apply the function to every alternative, e.g. to put the article before the noun
+    pub fn map(&self, f: impl Fn(&str) -> String) -> Self {
+        Self::from_alternatives(self.alternatives.iter().map(|a| f(a)).collect())
+    }
+}
+
+impl Display for AnswerSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.alternatives.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted(s: &str) -> Vec<String> {
+        AnswerSpec::parse(s).accepted
+    }
+
+    #[test]
+    fn alternatives_are_split_on_separators() {
+        let spec = AnswerSpec::parse("Bank, Sitzbank / Sessel;Stuhl");
+        assert_eq!(spec.alternatives(), ["Bank", "Sitzbank", "Sessel", "Stuhl"]);
+        assert_eq!(spec.primary(), "Bank");
+        assert_eq!(spec.to_string(), "Bank, Sitzbank, Sessel, Stuhl");
+        assert!(AnswerSpec::parse("  ").is_empty());
+    }
+
+    #[test]
+    fn optional_parts_are_expanded() {
+        assert_eq!(accepted("(sich) erinnern"), ["sich erinnern", "erinnern"]);
+        assert_eq!(
+            accepted("(sich) an (etw.) erinnern"),
+            [
+                "sich an etw. erinnern",
+                "an etw. erinnern",
+                "sich an erinnern",
+                "an erinnern"
+            ]
+        );
+        assert_eq!(accepted("Freund(in)"), ["Freundin", "Freund"]);
+        // a separator inside the parentheses does not split the alternatives
+        assert_eq!(accepted("gehen (zu Fuß, langsam)").len(), 2);
+    }
+
+    #[test]
+    fn too_many_optional_parts_are_taken_literally() {
+        assert_eq!(accepted("(a) (b) (c) (d) (e) f"), ["(a) (b) (c) (d) (e) f"]);
+        assert_eq!(accepted("(unclosed"), ["(unclosed"]);
+    }
+
+    #[test]
+    fn answers_are_matched() {
+        let spec = AnswerSpec::parse("(sich) erinnern, gedenken");
+        assert!(spec.matches("erinnern"));
+        assert!(spec.matches("sich  erinnern"));
+        assert!(spec.matches("gedenken"));
+        assert!(!spec.matches("vergessen"));
+        let spec = AnswerSpec::parse("ёлка/ель");
+        assert_eq!(spec.check_translation("Елка"), (Verdict::Correct, "ёлка"));
+        assert_eq!(
+            spec.check_translation("елк"),
+            (Verdict::NearMiss(Mistake::Typo), "ёлка")
+        );
+        assert_eq!(spec.check_translation("ель").0, Verdict::Correct);
+    }
+
+    #[test]
+    fn strict_parsing_reports_literal_parts() {
+        assert!(AnswerSpec::parse_strict("(sich) erinnern, gedenken").is_ok());
+        assert!(AnswerSpec::parse_strict("").is_ok());
+        let error = |s| AnswerSpec::parse_strict(s).unwrap_err();
+        assert!(error("(sich erinnern").contains("unbalanced"));
+        assert!(error("sich) erinnern").contains("unbalanced"));
+        assert!(error("((sich)) erinnern").contains("nested"));
+        assert!(error("Bank,, Sitzbank").contains("empty alternative"));
+        assert!(error("Bank /").contains("empty alternative"));
+        assert!(error("() erinnern").contains("empty optional"));
+        assert!(error("(a) (b) (c) (d) (e) f").contains("optional parts"));
+    }
+
+    #[test]
+    fn typo_distance_counts_swaps_as_one_edit() {
+        assert_eq!(typo_distance("hund", "hund"), 0);
+        assert_eq!(typo_distance("hnud", "hund"), 1);
+        assert_eq!(typo_distance("hun", "hund"), 1);
+        assert_eq!(typo_distance("hundd", "hund"), 1);
+        assert_eq!(typo_distance("hand", "hund"), 1);
+        assert_eq!(typo_distance("", "hund"), 4);
+        assert_eq!(max_typos("Hut"), 0);
+        assert_eq!(max_typos("Zimmer"), 1);
+        assert_eq!(max_typos("Wörterbuch"), 2);
+    }
+
+    #[test]
+    fn answers_are_classified() {
+        assert_eq!(classify("die Übung", "die Übung"), Verdict::Correct);
+        assert_eq!(
+            classify("die uebung", "die Übung"),
+            Verdict::NearMiss(Mistake::Capitalization)
+        );
+        assert_eq!(
+            classify("die Ubung", "die Übung"),
+            Verdict::NearMiss(Mistake::MissingUmlaut)
+        );
+        assert_eq!(
+            classify("der Übung", "die Übung"),
+            Verdict::NearMiss(Mistake::Article)
+        );
+        assert_eq!(
+            classify("die Übnug", "die Übung"),
+            Verdict::NearMiss(Mistake::Typo)
+        );
+        assert_eq!(classify("die Lösung", "die Übung"), Verdict::Wrong);
+        assert_eq!(classify("Hut", "Hat"), Verdict::Wrong);
+    }
+
+    #[test]
+    fn capitalized_article_is_not_a_mistake() {
+        assert_eq!(classify("Der Hund", "der Hund"), Verdict::Correct);
+        assert_eq!(classify("Dem alten", "dem alten"), Verdict::Correct);
+        assert_eq!(
+            classify("der hund", "der Hund"),
+            Verdict::NearMiss(Mistake::Capitalization)
+        );
+        let spec = AnswerSpec::from_alternatives(vec!["die Hunde".to_owned()]);
+        assert_eq!(spec.check("Die Hunde"), (Verdict::Correct, "die Hunde"));
+    }
+
+    #[test]
+    fn best_alternative_is_chosen() {
+        let spec = AnswerSpec::parse("Bank, Sitzbank");
+        assert_eq!(
+            spec.check("Sitzbnak"),
+            (Verdict::NearMiss(Mistake::Typo), "Sitzbank")
+        );
+        assert_eq!(spec.check("Stuhl"), (Verdict::Wrong, "Bank"));
+    }
+
+    #[test]
+    fn diff_aligns_the_letters() {
+        use DiffOp::*;
+        assert_eq!(
+            diff_ops("Ubung", "Übung"),
+            [
+                Replace('U', 'Ü'),
+                Same('b'),
+                Same('u'),
+                Same('n'),
+                Same('g')
+            ]
+        );
+        assert_eq!(
+            diff_ops("Hnd", "Hund"),
+            [Same('H'), Missing('u'), Same('n'), Same('d')]
+        );
+        assert_eq!(
+            diff_ops("Hunde", "Hund"),
+            [Same('H'), Same('u'), Same('n'), Same('d'), Extra('e')]
+        );
+        assert_eq!(diff_ops("", "ab"), [Missing('a'), Missing('b')]);
+    }
+}
@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use das_woerterbuch::exercise::ExerciseRegistry;
+use das_woerterbuch::language::Language;
+use das_woerterbuch::scheduler::SelectionStrategy;
+use das_woerterbuch::{fill, Locale, Msg};
+
+pub const DEFAULT_DICTIONARY: &str = "woerterbuch.xlsx";
+pub const DEFAULT_PROGRESS: &str = "exercises.bin";
+pub const DEFAULT_COUNT: usize = 10;
+
+/// Indentation of the option descriptions in the usage
+const USAGE_INDENT: usize = 24;
+const USAGE_WIDTH: usize = 80;
+
+/// Exercise modes of the default registry wrapped under the `--mode` option
+fn modes() -> String {
+    let registry = ExerciseRegistry::default();
+    let names: Vec<_> = std::iter::once("all")
+        .chain(registry.iter().map(|ex| ex.name()))
+        .collect();
+    let indent = " ".repeat(USAGE_INDENT);
+    let mut lines = vec![];
+    let mut line = indent.clone();
+    for (i, name) in names.iter().enumerate() {
+        let item = if i + 1 < names.len() {
+            format!("{},", name)
+        } else {
+            name.to_string()
+        };
+        if line.len() > USAGE_INDENT && line.len() + 1 + item.len() > USAGE_WIDTH {
+            lines.push(std::mem::replace(&mut line, indent.clone()));
+        }
+        if line.len() > USAGE_INDENT {
+            line.push(' ');
+        }
+        line.push_str(&item);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+/// Usage message in the interface language
+pub fn usage() -> String {
+    fill(
+        Msg::Usage,
+        &[
+            &DEFAULT_DICTIONARY,
+            &DEFAULT_PROGRESS,
+            &modes(),
+            &DEFAULT_COUNT,
+        ],
+    )
+}
+
+/// Interface language given with `--ui`, so that the errors of the other options
+/// are shown in it
+pub fn find_locale(args: &[String]) -> Locale {
+    args.iter()
+        .position(|arg| arg == "--ui")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|value| Locale::from_str(value).ok())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Train,
+    Stats,
+    List,
+    Lint,
+    Help,
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    pub dictionary: String,
+    pub progress: String,
+    pub count: usize,
+    /// Names of the exercises in the default registry
+    pub modes: Option<Vec<&'static str>>,
+    pub strategy: Option<SelectionStrategy>,
+    pub save_every: Option<usize>,
+    pub language: Language,
+    pub seed: Option<u64>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            command: Command::Train,
+            dictionary: DEFAULT_DICTIONARY.to_owned(),
+            progress: DEFAULT_PROGRESS.to_owned(),
+            count: DEFAULT_COUNT,
+            modes: None,
+            strategy: None,
+            save_every: None,
+            language: Language::default(),
+            seed: None,
+        }
+    }
+}
+
+fn parse_modes(s: &str) -> Result<Vec<&'static str>, String> {
+    let registry = ExerciseRegistry::default();
+    let mut modes = vec![];
+    for name in s.split(',').map(str::trim) {
+        if name == "all" {
+            modes.extend(registry.iter().map(|ex| ex.name()));
+        } else {
+            let exercise = registry
+                .get(name)
+                .ok_or_else(|| fill(Msg::UnknownMode, &[&format!("{:?}", name)]))?;
+            modes.push(exercise.name());
+        }
+    }
+    Ok(modes)
+}
+
+fn parse_number(option: &str, s: &str) -> Result<usize, String> {
+    match s.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(fill(
+            Msg::ExpectsPositiveNumber,
+            &[&option, &format!("{:?}", s)],
+        )),
+    }
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    let mut command = None;
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            if command.is_some() {
+                return Err(fill(Msg::UnexpectedArgument, &[&format!("{:?}", arg)]));
+            }
+            command = Some(match arg.as_str() {
+                "train" => Command::Train,
+                "stats" => Command::Stats,
+                "list" => Command::List,
+                "lint" => Command::Lint,
+                "help" => Command::Help,
+                _ => return Err(fill(Msg::UnknownCommand, &[&format!("{:?}", arg)])),
+            });
+            continue;
+        }
+        if arg == "--help" {
+            command = Some(Command::Help);
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| fill(Msg::OptionRequiresValue, &[&arg]))?;
+        match arg.as_str() {
+            "--dict" => parsed.dictionary = value,
+            "--progress" => parsed.progress = value,
+            "--count" => parsed.count = parse_number(&arg, &value)?,
+            "--save-every" => parsed.save_every = Some(parse_number(&arg, &value)?),
+            "--mode" => parsed
+                .modes
+                .get_or_insert_with(Vec::new)
+                .extend(parse_modes(&value)?),
+            "--language" => {
+                parsed.language = Language::from_str(&value)
+                    .map_err(|_| fill(Msg::UnknownLanguage, &[&format!("{:?}", value)]))?
+            }
+            "--ui" => {
+                // the locale is selected by find_locale before the parsing
+                Locale::from_str(&value)
+                    .map_err(|_| fill(Msg::UnknownUiLanguage, &[&format!("{:?}", value)]))?;
+            }
+            "--seed" => {
+                parsed.seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| fill(Msg::ExpectsNumber, &[&arg, &format!("{:?}", value)]))?,
+                )
+            }
+            "--strategy" => {
+                parsed.strategy = Some(
+                    SelectionStrategy::from_str(&value)
+                        .map_err(|_| fill(Msg::UnknownStrategy, &[&format!("{:?}", value)]))?,
+                )
+            }
+            _ => return Err(fill(Msg::UnknownOption, &[&arg])),
+        }
+    }
+    if let Some(command) = command {
+        parsed.command = command;
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_lists_every_registered_mode() {
+        let modes = modes();
+        for ex in ExerciseRegistry::default().iter() {
+            assert!(modes.contains(ex.name()), "{} is not listed", ex.name());
+        }
+        for line in modes.lines() {
+            assert!(line.len() <= USAGE_WIDTH);
+            assert!(line.starts_with(&" ".repeat(USAGE_INDENT)));
+        }
+    }
+}
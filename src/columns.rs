@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use calamine::{DataType, Range};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+
+use crate::language::Language;
+use crate::messages::{fill, Msg};
+
+/// Number of top rows searched for the header row
+const HEADER_SEARCH_ROWS: usize = 5;
+
+/// Known columns of the Words sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Column {
+    /// Identity of the word to keep the homonyms apart
+    Id,
+    /// German word, with the separable prefix marked by `|`
+    Word,
+    /// Part of speech: `n`, `v`, `adj`, `adv`, `prep`
+    PartOfSpeech,
+    /// Translation, to Russian unless the header names the language
+    Translation,
+    /// Topic of the word
+    Group,
+    /// Article of the noun
+    Article,
+    /// Plural of the noun
+    Plural,
+    /// Participle II of the verb
+    Perfect,
+    /// Präteritum of the verb
+    Praeteritum,
+    /// Hint shown in the translation exercise
+    Help,
+    /// Auxiliary verb of the perfect: `hat` or `ist`
+    PerfectVerb,
+    /// Third person singular of the present tense
+    PresentThird,
+    /// Case governed by the preposition
+    Case,
+    /// Comparative of the adjective
+    Comparative,
+    /// Superlative of the adjective
+    Superlative,
+    /// Preposition and case governed by the verb or the adjective
+    Government,
+}
+
+impl Column {
+    /// Column without which the sheet is not read
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            Column::Word | Column::PartOfSpeech | Column::Translation | Column::Group
+        )
+    }
+
+    fn default_aliases(self) -> &'static [&'static str] {
+        match self {
+            Column::Id => &["id"],
+            Column::Word => &["word", "wort"],
+            Column::PartOfSpeech => &["pos", "part of speech", "wortart"],
+            // "tranlation" is the spelling used in the original workbook
+            Column::Translation => &["translation", "tranlation"],
+            Column::Group => &["group", "topic", "gruppe"],
+            Column::Article => &["article", "artikel"],
+            Column::Plural => &["plural", "mehrzahl"],
+            Column::Perfect => &["perfect ii", "perfect", "perfekt", "partizip ii"],
+            Column::Praeteritum => &["praeteritum", "präteritum"],
+            Column::Help => &["help", "hint", "prefix"],
+            Column::PerfectVerb => &["perfect verb", "auxiliary", "hilfsverb"],
+            Column::PresentThird => &["3rd single", "present third", "präsens er"],
+            Column::Case => &["case", "kasus"],
+            Column::Comparative => &["comparative", "komparativ"],
+            Column::Superlative => &["superlative", "superlativ"],
+            Column::Government => &["government", "rektion"],
+        }
+    }
+}
+
+fn normalize_header(s: &str) -> String {
+    s.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Header names recognized for every column
+pub struct ColumnAliases {
+    aliases: HashMap<String, Column>,
+}
+
+impl Default for ColumnAliases {
+    fn default() -> Self {
+        let mut aliases = Self {
+            aliases: HashMap::new(),
+        };
+        for column in Column::iter() {
+            for alias in column.default_aliases() {
+                aliases.add_alias(column, alias);
+            }
+        }
+        aliases
+    }
+}
+
+impl ColumnAliases {
+    pub fn add_alias(&mut self, column: Column, alias: &str) {
+        self.aliases.insert(normalize_header(alias), column);
+    }
+
+    /// Read additional aliases from a sheet with rows of column key and header name,
+    /// e.g. `translation | Übersetzung`
+    pub fn add_from_range(&mut self, range: &Range<DataType>) -> Result<(), String> {
+        for (i, row) in range.rows().enumerate() {
+            let key = row.first().map(|c| c.to_string()).unwrap_or_default();
+            let alias = row.get(1).map(|c| c.to_string()).unwrap_or_default();
+            if key.trim().is_empty() || alias.trim().is_empty() {
+                continue;
+            }
+            let column = Column::from_str(key.trim())
+                .map_err(|_| fill(Msg::UnknownAliasColumn, &[&format!("{:?}", key), &(i + 1)]))?;
+            self.add_alias(column, &alias);
+        }
+        Ok(())
+    }
+
+    fn find(&self, header: &str) -> Option<Column> {
+        self.aliases.get(&normalize_header(header)).copied()
+    }
+
+    /// Language of the translation column by the code in the header: `Translation en`,
+    /// `Translation (en)`, `Translation:en`
+    fn find_translation(&self, header: &str) -> Option<Language> {
+        let header = normalize_header(header);
+        let header = header.strip_suffix(')').unwrap_or(&header);
+        let (name, code) = header.rsplit_once([' ', ':', '('])?;
+        let name = name.trim_end_matches([' ', ':', '(']);
+        let language = Language::from_str(code).ok()?;
+        (self.find(name) == Some(Column::Translation)).then_some(language)
+    }
+}
+
+/// Positions of the known and user defined columns found in the header row
+pub struct ColumnMap {
+    header_row: usize,
+    columns: HashMap<Column, usize>,
+    translations: HashMap<Language, usize>,
+    extra: Vec<(usize, String)>,
+}
+
+impl ColumnMap {
+    fn from_header(header_row: usize, header: &[DataType], aliases: &ColumnAliases) -> Self {
+        let mut columns = HashMap::new();
+        let mut translations = HashMap::new();
+        let mut extra = vec![];
+        for (idx, cell) in header.iter().enumerate() {
+            let name = cell.to_string();
+            if name.trim().is_empty() {
+                continue;
+            }
+            match aliases.find(&name) {
+                Some(Column::Translation) => {
+                    translations.entry(Language::default()).or_insert(idx);
+                }
+                Some(column) => {
+                    columns.entry(column).or_insert(idx);
+                }
+                None => match aliases.find_translation(&name) {
+                    Some(language) => {
+                        translations.entry(language).or_insert(idx);
+                    }
+                    None => extra.push((idx, name.trim().to_owned())),
+                },
+            }
+        }
+        Self {
+            header_row,
+            columns,
+            translations,
+            extra,
+        }
+    }
+
+    fn has_column(&self, column: Column) -> bool {
+        match column {
+            Column::Translation => !self.translations.is_empty(),
+            _ => self.columns.contains_key(&column),
+        }
+    }
+
+    fn missing_required(&self) -> Vec<Column> {
+        Column::iter()
+            .filter(|c| c.is_required() && !self.has_column(*c))
+            .collect()
+    }
+
+    /// Find the header row among the top rows of the sheet
+    pub fn from_range(range: &Range<DataType>, aliases: &ColumnAliases) -> Result<Self, String> {
+        let mut best: Option<ColumnMap> = None;
+        for (i, row) in range.rows().take(HEADER_SEARCH_ROWS).enumerate() {
+            let map = Self::from_header(i, row, aliases);
+            if map.missing_required().is_empty() {
+                return Ok(map);
+            }
+            if best
+                .as_ref()
+                .is_none_or(|b| map.columns.len() > b.columns.len())
+            {
+                best = Some(map);
+            }
+        }
+        let missing: Vec<&str> = match best {
+            Some(map) => map
+                .missing_required()
+                .into_iter()
+                .map(|c| c.into())
+                .collect(),
+            None => Column::iter()
+                .filter(|c| c.is_required())
+                .map(|c| c.into())
+                .collect(),
+        };
+        Err(fill(Msg::MissingColumns, &[&missing.join(", ")]))
+    }
+
+    /// Index of the first row after the header
+    pub fn first_data_row(&self) -> usize {
+        self.header_row + 1
+    }
+
+    pub fn row(&self, row: &[DataType]) -> WordRow {
+        let cell = |idx: usize| row.get(idx).map(|c| c.to_string()).unwrap_or_default();
+        WordRow {
+            cells: self
+                .columns
+                .iter()
+                .map(|(&column, &idx)| (column, cell(idx)))
+                .collect(),
+            translations: self
+                .translations
+                .iter()
+                .map(|(&language, &idx)| (language, cell(idx)))
+                .filter(|(_, value)| !value.trim().is_empty())
+                .collect(),
+            extra: self
+                .extra
+                .iter()
+                .map(|(idx, name)| (name.to_owned(), cell(*idx)))
+                .filter(|(_, value)| !value.trim().is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Cells of a single word row addressed by column
+pub struct WordRow {
+    cells: HashMap<Column, String>,
+    /// Non-empty translations keyed by language
+    pub translations: HashMap<Language, String>,
+    /// Cells of the user defined columns by their header
+    pub extra: HashMap<String, String>,
+}
+
+impl WordRow {
+    /// Cell of the column, empty if the column is absent
+    pub fn get(&self, column: Column) -> &str {
+        self.cells
+            .get(&column)
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Row has a translation to any language
+    pub fn has_translation(&self) -> bool {
+        !self.translations.is_empty()
+    }
+
+    /// Take the cell out of the row, `None` if the column is absent in the sheet
+    pub fn take(&mut self, column: Column) -> Option<String> {
+        self.cells.remove(&column)
+    }
+}
+
+/// Sheet with the text cells for the tests
+#[cfg(test)]
+pub(crate) fn sheet(rows: &[&[&str]]) -> Range<DataType> {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or_default();
+    let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width as u32 - 1));
+    for (i, row) in rows.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            range.set_value((i as u32, j as u32), DataType::String(cell.to_string()));
+        }
+    }
+    range
+}
+
+/// Single row of a sheet with the header for the tests
+#[cfg(test)]
+pub(crate) fn word_row(header: &[&str], cells: &[&str]) -> WordRow {
+    let range = sheet(&[header, cells]);
+    let columns = ColumnMap::from_range(&range, &ColumnAliases::default()).unwrap();
+    columns.row(range.rows().nth(1).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(header: &[&str]) -> ColumnMap {
+        ColumnMap::from_range(&sheet(&[header]), &ColumnAliases::default()).unwrap()
+    }
+
+    #[test]
+    fn translation_columns_are_found_by_language() {
+        let map = columns(&["Word", "PoS", "Group", "Translation", "Translation (en)"]);
+        let en = Language::from_str("en").unwrap();
+        assert_eq!(map.translations[&Language::default()], 3);
+        assert_eq!(map.translations[&en], 4);
+        let map = columns(&["Word", "PoS", "Group", "Translation:EN"]);
+        assert_eq!(map.translations[&en], 3);
+    }
+
+    #[test]
+    fn any_language_code_is_a_translation() {
+        let map = columns(&[
+            "Word",
+            "PoS",
+            "Group",
+            "Translation",
+            "Translation uk",
+            "Translation (de)",
+        ]);
+        assert_eq!(map.translations[&Language::from_str("uk").unwrap()], 4);
+        assert_eq!(map.translations[&Language::from_str("de").unwrap()], 5);
+        assert!(map.extra.is_empty());
+        // the columns which only start with the name are not translations
+        let map = columns(&["Word", "PoS", "Group", "Translation", "Translation notes"]);
+        assert_eq!(map.translations.len(), 1);
+        assert_eq!(map.extra, [(4, "Translation notes".to_owned())]);
+    }
+}
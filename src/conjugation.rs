@@ -0,0 +1,286 @@
+//! Conjugation of the verbs in the present tense and Präteritum
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+
+/// Grammatical person and number of the conjugated verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Person {
+    /// First person singular
+    Ich,
+    /// Second person singular
+    Du,
+    /// Third person singular
+    Er,
+    /// First person plural
+    Wir,
+    /// Second person plural
+    Ihr,
+    /// Third person plural and the polite form
+    Sie,
+}
+
+impl Person {
+    /// Pronoun of the reflexive verbs: ich freue mich
+    pub fn reflexive_pronoun(self) -> &'static str {
+        match self {
+            Self::Ich => "mich",
+            Self::Du => "dich",
+            Self::Er | Self::Sie => "sich",
+            Self::Wir => "uns",
+            Self::Ihr => "euch",
+        }
+    }
+}
+
+impl Display for Person {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ich => "ich",
+            Self::Du => "du",
+            Self::Er => "er",
+            Self::Wir => "wir",
+            Self::Ihr => "ihr",
+            Self::Sie => "sie",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Tense of the conjugation exercise
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Tense {
+    /// Präsens: du liest
+    Present,
+    /// Präteritum: ihr wart
+    Praeteritum,
+}
+
+impl Display for Tense {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Present => "Präsens",
+            Self::Praeteritum => "Präteritum",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Present tense of the verbs which cannot be derived from the stored forms
+const IRREGULAR_PRESENT: [(&str, [&str; 6]); 3] = [
+    ("sein", ["bin", "bist", "ist", "sind", "seid", "sind"]),
+    ("haben", ["habe", "hast", "hat", "haben", "habt", "haben"]),
+    (
+        "werden",
+        ["werde", "wirst", "wird", "werden", "werdet", "werden"],
+    ),
+];
+
+/// Infinitive without the -en or -n ending: machen - mach, wandern - wander, tun - tu
+fn infinitive_stem(infinitive: &str) -> &str {
+    infinitive
+        .strip_suffix("en")
+        .or_else(|| infinitive.strip_suffix('n'))
+        .unwrap_or(infinitive)
+}
+
+/// Stems which need -e- before the consonant endings: arbeit-et, find-et, öffn-et, atm-et
+fn needs_e(stem: &str) -> bool {
+    stem.ends_with('t')
+        || stem.ends_with('d')
+        || ["chn", "ffn", "gn", "tm", "dm"]
+            .iter()
+            .any(|s| stem.ends_with(s))
+}
+
+fn ends_with_sibilant(s: &str) -> bool {
+    ["s", "ß", "z", "x"].iter().any(|e| s.ends_with(e))
+}
+
+/// Add the ending starting with -t or -st, inserting -e- where needed
+fn add_t_ending(stem: &str, ending: &str) -> String {
+    if needs_e(stem) {
+        format!("{}e{}", stem, ending)
+    } else if ending == "st" && ends_with_sibilant(stem) {
+        format!("{}t", stem)
+    } else {
+        format!("{}{}", stem, ending)
+    }
+}
+
+fn conjugate_present(infinitive: &str, third: &str, person: Person) -> String {
+    let stem = infinitive_stem(infinitive);
+    let third = third.to_owned();
+    // modal verbs and wissen: er muss, er will, er weiß
+    let modal = !third.ends_with('t');
+    match person {
+        Person::Ich if modal => third,
+        // sammeln - ich sammle
+        Person::Ich if stem.ends_with("el") => format!("{}le", &stem[..stem.len() - 2]),
+        Person::Ich => format!("{}e", stem),
+        Person::Du if modal => add_t_ending(&third, "st"),
+        Person::Du => {
+            if let Some(stem) = third.strip_suffix("et").filter(|s| needs_e(s)) {
+                // er arbeitet - du arbeitest
+                format!("{}est", stem)
+            } else if stem.ends_with('t') {
+                // er hält - du hältst
+                format!("{}st", third)
+            } else {
+                let changed = third.strip_suffix('t').unwrap_or(&third);
+                if ends_with_sibilant(changed) {
+                    third
+                } else {
+                    format!("{}st", changed)
+                }
+            }
+        }
+        Person::Er => third,
+        Person::Ihr => add_t_ending(stem, "t"),
+        Person::Wir | Person::Sie => infinitive.to_owned(),
+    }
+}
+
+fn conjugate_praeteritum(past: &str, person: Person) -> String {
+    let past = past.to_owned();
+    let weak = past.ends_with('e');
+    match person {
+        Person::Ich | Person::Er => past,
+        Person::Du if weak => format!("{}st", past),
+        // du lasest, du saßest, du schmolzest
+        Person::Du if past.ends_with(['s', 'ß', 'z']) => format!("{}est", past),
+        Person::Du => add_t_ending(&past, "st"),
+        Person::Ihr if weak => format!("{}t", past),
+        Person::Ihr => add_t_ending(&past, "t"),
+        Person::Wir | Person::Sie if weak => format!("{}n", past),
+        Person::Wir | Person::Sie => format!("{}en", past),
+    }
+}
+
+/// Personal form of the verb derived from the infinitive and the stored form of the third
+/// person in the tense: lesen, liest, las - du liest, ihr last. `None` if the form is not
+/// stored, the strong and mixed verbs cannot be told from the regular ones without it
+pub fn conjugate(
+    infinitive: &str,
+    present_third: Option<&str>,
+    praeteritum: Option<&str>,
+    tense: Tense,
+    person: Person,
+) -> Option<String> {
+    let infinitive = infinitive.trim();
+    if let Some((_, forms)) = IRREGULAR_PRESENT.iter().find(|(inf, _)| *inf == infinitive) {
+        if tense == Tense::Present {
+            return Some(forms[person as usize].to_owned());
+        }
+    }
+    let stored = match tense {
+        Tense::Present => present_third,
+        Tense::Praeteritum => praeteritum,
+    };
+    let stored = stored.map(str::trim).filter(|s| !s.is_empty())?;
+    Some(match tense {
+        Tense::Present => conjugate_present(infinitive, stored, person),
+        Tense::Praeteritum => conjugate_praeteritum(stored, person),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    fn forms(infinitive: &str, third: &str, past: &str, tense: Tense) -> Vec<String> {
+        Person::iter()
+            .map(|p| conjugate(infinitive, Some(third), Some(past), tense, p).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn regular_verb() {
+        let present = forms("machen", "macht", "machte", Tense::Present);
+        assert_eq!(
+            present,
+            ["mache", "machst", "macht", "machen", "macht", "machen"]
+        );
+        let past = forms("machen", "macht", "machte", Tense::Praeteritum);
+        assert_eq!(
+            past,
+            ["machte", "machtest", "machte", "machten", "machtet", "machten"]
+        );
+    }
+
+    #[test]
+    fn strong_verbs() {
+        let present = forms("lesen", "liest", "las", Tense::Present);
+        assert_eq!(
+            present,
+            ["lese", "liest", "liest", "lesen", "lest", "lesen"]
+        );
+        let past = forms("lesen", "liest", "las", Tense::Praeteritum);
+        assert_eq!(past, ["las", "lasest", "las", "lasen", "last", "lasen"]);
+        let present = forms("fahren", "fährt", "fuhr", Tense::Present);
+        assert_eq!(present[1], "fährst");
+        let present = forms("halten", "hält", "hielt", Tense::Present);
+        assert_eq!(present[1], "hältst");
+        assert_eq!(present[4], "haltet");
+    }
+
+    #[test]
+    fn mixed_and_modal_verbs() {
+        let past = forms("denken", "denkt", "dachte", Tense::Praeteritum);
+        assert_eq!(past[1], "dachtest");
+        let present = forms("können", "kann", "konnte", Tense::Present);
+        assert_eq!(present[..3], ["kann", "kannst", "kann"]);
+        let present = forms("müssen", "muss", "musste", Tense::Present);
+        assert_eq!(present[1], "musst");
+    }
+
+    #[test]
+    fn stems_ending_in_t_or_d() {
+        let present = forms("arbeiten", "arbeitet", "arbeitete", Tense::Present);
+        assert_eq!(present[1], "arbeitest");
+        assert_eq!(present[4], "arbeitet");
+        let past = forms("arbeiten", "arbeitet", "arbeitete", Tense::Praeteritum);
+        assert_eq!(past[1], "arbeitetest");
+        let past = forms("finden", "findet", "fand", Tense::Praeteritum);
+        assert_eq!(
+            past,
+            ["fand", "fandest", "fand", "fanden", "fandet", "fanden"]
+        );
+    }
+
+    #[test]
+    fn missing_form_is_not_guessed() {
+        assert_eq!(
+            conjugate("singen", None, None, Tense::Praeteritum, Person::Ich),
+            None
+        );
+        assert_eq!(
+            conjugate("singen", None, Some("sang"), Tense::Present, Person::Du),
+            None
+        );
+        assert_eq!(
+            conjugate("sein", None, None, Tense::Present, Person::Du).as_deref(),
+            Some("bist")
+        );
+        assert_eq!(
+            conjugate("sein", None, None, Tense::Praeteritum, Person::Du),
+            None
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Declension of the articles and the adjectives by case
+
+use std::fmt::Display;
+
+use strum_macros::EnumIter;
+
+use crate::messages::{text, Msg};
+use crate::words::{NounArticle, PrepositionCase};
+
+/// Grammatical case of a noun phrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Case {
+    /// Subject: der Hund
+    Nominativ,
+    /// Direct object: den Hund
+    Akkusativ,
+    /// Indirect object: dem Hund
+    Dativ,
+    /// Possessive: des Hundes
+    Genitiv,
+}
+
+impl Case {
+    /// Case by its full or short name: `Akk`, `Dativ`
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s.trim().to_lowercase().as_str() {
+            "nom" | "nominativ" => Case::Nominativ,
+            "akk" | "akkusativ" => Case::Akkusativ,
+            "dat" | "dativ" => Case::Dativ,
+            "gen" | "genitiv" => Case::Genitiv,
+            _ => return None,
+        })
+    }
+
+    /// Short name used in the dictionary cells: `Akk`
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Case::Nominativ => "Nom",
+            Case::Akkusativ => "Akk",
+            Case::Dativ => "Dat",
+            Case::Genitiv => "Gen",
+        }
+    }
+
+    /// Check if the preposition can be followed by the case
+    pub fn is_governed_by(self, prep: PrepositionCase) -> bool {
+        matches!(
+            (self, prep),
+            (Case::Akkusativ, PrepositionCase::Akkusativ)
+                | (Case::Dativ, PrepositionCase::Dativ)
+                | (Case::Genitiv, PrepositionCase::Genitiv)
+                | (Case::Akkusativ | Case::Dativ, PrepositionCase::Wechsel)
+        )
+    }
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Nominativ => "Nominativ",
+            Self::Akkusativ => "Akkusativ",
+            Self::Dativ => "Dativ",
+            Self::Genitiv => "Genitiv",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Article before the adjective, it defines the adjective ending
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum ArticleType {
+    /// der, die, das
+    Definite,
+    /// ein, eine
+    Indefinite,
+    /// No article: kaltes Wasser
+    Zero,
+}
+
+impl Display for ArticleType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::Definite => Msg::DefiniteArticle,
+            Self::Indefinite => Msg::IndefiniteArticle,
+            Self::Zero => Msg::NoArticle,
+        };
+        write!(f, "{}", text(msg))
+    }
+}
+
+/// Gender and number of the noun, the columns of the declension tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+    Plural,
+}
+
+impl From<NounArticle> for Gender {
+    fn from(article: NounArticle) -> Self {
+        match article {
+            NounArticle::Der => Gender::Masculine,
+            NounArticle::Die => Gender::Feminine,
+            NounArticle::Das => Gender::Neuter,
+            NounArticle::Plural => Gender::Plural,
+        }
+    }
+}
+
+// Rows are cases in the order of `Case`, columns are genders in the order of `Gender`
+
+const DEFINITE_ARTICLES: [[&str; 4]; 4] = [
+    ["der", "die", "das", "die"],
+    ["den", "die", "das", "die"],
+    ["dem", "der", "dem", "den"],
+    ["des", "der", "des", "der"],
+];
+
+const INDEFINITE_ARTICLES: [[&str; 4]; 4] = [
+    ["ein", "eine", "ein", ""],
+    ["einen", "eine", "ein", ""],
+    ["einem", "einer", "einem", ""],
+    ["eines", "einer", "eines", ""],
+];
+
+/// Adjective endings after the definite article
+const WEAK_ENDINGS: [[&str; 4]; 4] = [
+    ["e", "e", "e", "en"],
+    ["en", "e", "e", "en"],
+    ["en", "en", "en", "en"],
+    ["en", "en", "en", "en"],
+];
+
+/// Adjective endings after the indefinite article
+const MIXED_ENDINGS: [[&str; 4]; 4] = [
+    ["er", "e", "es", "en"],
+    ["en", "e", "es", "en"],
+    ["en", "en", "en", "en"],
+    ["en", "en", "en", "en"],
+];
+
+/// Adjective endings without article
+const STRONG_ENDINGS: [[&str; 4]; 4] = [
+    ["er", "e", "es", "e"],
+    ["en", "e", "es", "e"],
+    ["em", "er", "em", "en"],
+    ["en", "er", "en", "er"],
+];
+
+/// Check if the article type can be used with the noun, there is no indefinite plural article
+pub fn is_article_applicable(article_type: ArticleType, noun_article: NounArticle) -> bool {
+    !(article_type == ArticleType::Indefinite && noun_article == NounArticle::Plural)
+}
+
+/// Stem to which the ending is added: dunkel - dunkl-, teuer - teur-, hoch - hoh-, leise - leis-
+fn adjective_stem(adjective: &str) -> String {
+    if adjective == "hoch" {
+        "hoh".to_owned()
+    } else if let Some(stem) = adjective.strip_suffix("el") {
+        format!("{}l", stem)
+    } else if adjective.ends_with("auer") || adjective.ends_with("euer") {
+        format!("{}r", adjective.strip_suffix("er").unwrap())
+    } else {
+        adjective.strip_suffix('e').unwrap_or(adjective).to_owned()
+    }
+}
+
+/// Declined article and adjective of the noun phrase: "dem alten" (Hund).
+/// The noun itself is not declined, its ending depends on the noun class
+/// (dem Jungen, des Namens) which the dictionary does not store
+pub fn decline_phrase(
+    article_type: ArticleType,
+    case: Case,
+    noun_article: NounArticle,
+    adjective: &str,
+) -> String {
+    let gender = Gender::from(noun_article);
+    let (c, g) = (case as usize, gender as usize);
+    let (article, endings) = match article_type {
+        ArticleType::Definite => (DEFINITE_ARTICLES[c][g], &WEAK_ENDINGS),
+        ArticleType::Indefinite if gender != Gender::Plural => {
+            (INDEFINITE_ARTICLES[c][g], &MIXED_ENDINGS)
+        }
+        _ => ("", &STRONG_ENDINGS),
+    };
+    let adjective = adjective_stem(adjective.trim()) + endings[c][g];
+    if article.is_empty() {
+        adjective
+    } else {
+        format!("{} {}", article, adjective)
+    }
+}
+
+/// Preposition merged with the definite article: in dem - im, zu der - zur
+const CONTRACTIONS: [(&str, &str, &str); 8] = [
+    ("an", "dem", "am"),
+    ("an", "das", "ans"),
+    ("bei", "dem", "beim"),
+    ("in", "dem", "im"),
+    ("in", "das", "ins"),
+    ("von", "dem", "vom"),
+    ("zu", "dem", "zum"),
+    ("zu", "der", "zur"),
+];
+
+/// Phrase after the preposition contracted with its article: "im alten", `None` if
+/// the preposition and the article are not contracted
+pub fn contract_preposition(preposition: &str, phrase: &str) -> Option<String> {
+    let (article, rest) = phrase.split_once(' ')?;
+    CONTRACTIONS
+        .iter()
+        .find(|(p, a, _)| *p == preposition.trim() && *a == article)
+        .map(|(_, _, contracted)| format!("{} {}", contracted, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decline(article_type: ArticleType, case: Case, article: NounArticle) -> String {
+        decline_phrase(article_type, case, article, "alt")
+    }
+
+    #[test]
+    fn definite_article_takes_weak_endings() {
+        use ArticleType::Definite;
+        assert_eq!(
+            decline(Definite, Case::Nominativ, NounArticle::Der),
+            "der alte"
+        );
+        assert_eq!(
+            decline(Definite, Case::Akkusativ, NounArticle::Der),
+            "den alten"
+        );
+        assert_eq!(
+            decline(Definite, Case::Akkusativ, NounArticle::Die),
+            "die alte"
+        );
+        assert_eq!(
+            decline(Definite, Case::Dativ, NounArticle::Das),
+            "dem alten"
+        );
+        assert_eq!(
+            decline(Definite, Case::Genitiv, NounArticle::Die),
+            "der alten"
+        );
+        assert_eq!(
+            decline(Definite, Case::Dativ, NounArticle::Plural),
+            "den alten"
+        );
+    }
+
+    #[test]
+    fn indefinite_article_takes_mixed_endings() {
+        use ArticleType::Indefinite;
+        assert_eq!(
+            decline(Indefinite, Case::Nominativ, NounArticle::Der),
+            "ein alter"
+        );
+        assert_eq!(
+            decline(Indefinite, Case::Nominativ, NounArticle::Das),
+            "ein altes"
+        );
+        assert_eq!(
+            decline(Indefinite, Case::Akkusativ, NounArticle::Das),
+            "ein altes"
+        );
+        assert_eq!(
+            decline(Indefinite, Case::Dativ, NounArticle::Die),
+            "einer alten"
+        );
+        assert_eq!(
+            decline(Indefinite, Case::Genitiv, NounArticle::Der),
+            "eines alten"
+        );
+        assert!(!is_article_applicable(Indefinite, NounArticle::Plural));
+    }
+
+    #[test]
+    fn zero_article_takes_strong_endings() {
+        use ArticleType::Zero;
+        assert_eq!(decline(Zero, Case::Nominativ, NounArticle::Der), "alter");
+        assert_eq!(decline(Zero, Case::Dativ, NounArticle::Das), "altem");
+        assert_eq!(decline(Zero, Case::Genitiv, NounArticle::Der), "alten");
+        assert_eq!(decline(Zero, Case::Nominativ, NounArticle::Plural), "alte");
+        assert_eq!(decline(Zero, Case::Genitiv, NounArticle::Plural), "alter");
+        assert_eq!(decline(Zero, Case::Dativ, NounArticle::Plural), "alten");
+    }
+
+    #[test]
+    fn adjective_stems() {
+        let decline = |adjective| {
+            decline_phrase(
+                ArticleType::Definite,
+                Case::Nominativ,
+                NounArticle::Die,
+                adjective,
+            )
+        };
+        assert_eq!(decline("dunkel"), "die dunkle");
+        assert_eq!(decline("teuer"), "die teure");
+        assert_eq!(decline("hoch"), "die hohe");
+        assert_eq!(decline("leise"), "die leise");
+    }
+
+    #[test]
+    fn contractions() {
+        assert_eq!(
+            contract_preposition("in", "dem alten").as_deref(),
+            Some("im alten")
+        );
+        assert_eq!(
+            contract_preposition("an", "dem alten").as_deref(),
+            Some("am alten")
+        );
+        assert_eq!(
+            contract_preposition("zu", "der alten").as_deref(),
+            Some("zur alten")
+        );
+        assert_eq!(
+            contract_preposition("bei", "dem alten").as_deref(),
+            Some("beim alten")
+        );
+        assert_eq!(
+            contract_preposition("von", "dem alten").as_deref(),
+            Some("vom alten")
+        );
+        assert_eq!(contract_preposition("in", "den alten"), None);
+        assert_eq!(contract_preposition("mit", "dem alten"), None);
+        assert_eq!(contract_preposition("in", "altem"), None);
+    }
+}
@@ -1,30 +1,267 @@
-use std::collections::HashMap;
+//! Loading of the dictionary from the Excel workbook
 
+use std::collections::{BTreeMap, HashMap};
+
+use crate::columns::{Column, ColumnAliases, ColumnMap};
+use crate::error::Error;
+use crate::language::Language;
+use crate::messages::{fill, Msg};
+pub use crate::validation::RowIssue;
+use crate::validation::{validate_row, RowKind};
 use crate::words::*;
-use calamine::{open_workbook, Reader, Xlsx};
+use calamine::{open_workbook, DataType, Range, Reader, Xlsx};
+
+const WORDS_SHEET: &str = "Words";
+const COLUMNS_SHEET: &str = "Columns";
+
+/// Load the dictionary and collect the problems of all rows which were skipped
+pub fn read_database(filename: &str) -> Result<(Database, Vec<RowIssue>), Error> {
+    let mut excel: Xlsx<_> = open_workbook(filename)
+        .map_err(|e| Error::Workbook(fill(Msg::CannotOpenWorkbook, &[&filename, &e])))?;
+
+    let mut aliases = ColumnAliases::default();
+    if let Some(Ok(r)) = excel.worksheet_range(COLUMNS_SHEET) {
+        aliases.add_from_range(&r).map_err(Error::Parse)?;
+    }
+
+    let r = match excel.worksheet_range(WORDS_SHEET) {
+        Some(Ok(r)) => r,
+        Some(Err(e)) => {
+            return Err(Error::Workbook(fill(
+                Msg::CannotReadSheet,
+                &[&WORDS_SHEET, &e],
+            )))
+        }
+        None => {
+            return Err(Error::Workbook(fill(
+                Msg::NoSheet,
+                &[&WORDS_SHEET, &filename],
+            )))
+        }
+    };
+    let columns = ColumnMap::from_range(&r, &aliases).map_err(Error::Parse)?;
+    Ok(read_words(&r, &columns))
+}
 
-pub fn fill_database(filename: &str) -> Database {
-    let mut excel: Xlsx<_> = open_workbook(filename).unwrap();
-    let r = excel.worksheet_range("Words").unwrap().unwrap();
+/// Create the words from the rows of the Words sheet. An earlier homonym with the same
+/// identity is told apart by [`homonym_suffix`], a real duplicate is replaced by the later row
+fn read_words(r: &Range<DataType>, columns: &ColumnMap) -> (Database, Vec<RowIssue>) {
+    let first_row = r.start().map(|(row, _)| row as usize).unwrap_or_default() + 1;
 
     let mut db = Database {
         groups: vec![],
-        words: HashMap::new(),
+        words: BTreeMap::new(),
     };
-    for row in r.rows().skip(2) {
-        let mut map: HashMap<usize, String> =
-            row.iter().map(|dt| dt.to_string()).enumerate().collect();
-        let pos = get_part_of_speech(&map);
-        let word = match pos {
-            "n" => Box::new(Noun::new(&mut map, &mut db)) as Box<dyn Word>,
-            "v" => Box::new(Verb::new(&mut map, &mut db)),
-            "adj" => Box::new(Adjective::new(&mut map, &mut db)),
-            "adv" => Box::new(Adverb::new(&mut map, &mut db)),
-            "prep" => Box::new(Preposition::new(&mut map, &mut db)),
+    let mut issues = vec![];
+    let mut word_rows: HashMap<String, (usize, bool)> = HashMap::new();
+    for (i, row) in r.rows().enumerate().skip(columns.first_data_row()) {
+        let mut row = columns.row(row);
+        let name = row.get(Column::Word).trim().to_owned();
+        let explicit_id = !row.get(Column::Id).trim().is_empty();
+        let issue = |message: String| RowIssue {
+            row: first_row + i,
+            word: name.clone(),
+            message,
+        };
+        match validate_row(&row) {
+            Ok(RowKind::Word) => {}
+            Ok(RowKind::Ignored) => continue,
+            Err(problems) => {
+                issues.extend(problems.into_iter().map(issue));
+                continue;
+            }
+        }
+        let pos = get_part_of_speech(&row).trim().to_owned();
+        let word = match pos.as_str() {
+            "n" => Noun::new(&mut row, &mut db).map(|w| Box::new(w) as Box<dyn Word>),
+            "v" => Verb::new(&mut row, &mut db).map(|w| Box::new(w) as Box<dyn Word>),
+            "adj" => Adjective::new(&mut row, &mut db).map(|w| Box::new(w) as Box<dyn Word>),
+            "adv" => Adverb::new(&mut row, &mut db).map(|w| Box::new(w) as Box<dyn Word>),
+            "prep" => Preposition::new(&mut row, &mut db).map(|w| Box::new(w) as Box<dyn Word>),
             _ => continue,
         };
-        db.words.insert(word.get_word().to_owned(), word);
+        let word = match word {
+            Ok(word) => word,
+            Err(e) => {
+                issues.push(issue(e));
+                continue;
+            }
+        };
+        let id = word.get_id().to_owned();
+        if let Some((prev_row, prev_explicit)) = word_rows.remove(&id) {
+            let mut earlier = db.words.remove(&id).unwrap();
+            let renamed = (!explicit_id && !prev_explicit)
+                .then(|| homonym_suffix(earlier.as_ref(), word.as_ref(), &db))
+                .flatten()
+                .map(|suffix| format!("{}:{}", id, suffix))
+                .filter(|renamed| !db.words.contains_key(renamed));
+            match renamed {
+                Some(renamed) => {
+                    earlier.set_id(renamed.clone());
+                    word_rows.insert(renamed.clone(), (prev_row, false));
+                    db.words.insert(renamed, earlier);
+                }
+                None => issues.push(RowIssue {
+                    row: prev_row,
+                    word: earlier.get_word().trim().to_owned(),
+                    message: fill(
+                        Msg::DuplicateWord,
+                        &[&format!("{:?}", id), &(first_row + i)],
+                    ),
+                }),
+            }
+        }
+        word_rows.insert(id.clone(), (first_row + i, explicit_id));
+        db.words.insert(id, word);
+    }
+    inherit_base_verb_forms(&mut db);
+    issues.sort_by_key(|issue| issue.row);
+
+    (db, issues)
+}
+
+/// Part of the identity which tells the earlier of two homonyms apart: its translation,
+/// or its group when both are translated the same, e.g. `v:raten:угадывать`
+fn homonym_suffix(earlier: &dyn Word, later: &dyn Word, db: &Database) -> Option<String> {
+    let lang = Language::default();
+    let translation = earlier.translation(lang).trim();
+    let suffix = if translation != later.translation(lang).trim() {
+        translation
+    } else if earlier.get_group_id() != later.get_group_id() {
+        db.groups[earlier.get_group_id()].trim()
+    } else {
+        return None;
+    };
+    Some(suffix.to_lowercase()).filter(|s| !s.is_empty())
+}
+
+/// Fill the forms of the separable verbs from their base verbs found in the dictionary
+fn inherit_base_verb_forms(db: &mut Database) {
+    let derived: Vec<(String, String)> = db
+        .words
+        .values()
+        .filter_map(|w| {
+            let parts = w.get_verb_parts()?;
+            parts.prefix.as_ref()?;
+            let base_id = make_word_id(PartOfSpeech::Verb, None, &parts.infinitive);
+            Some((w.get_id().to_owned(), base_id))
+        })
+        .filter(|(_, base_id)| db.words.contains_key(base_id))
+        .collect();
+    for (id, base_id) in derived {
+        if let Some(mut word) = db.words.remove(&id) {
+            word.inherit_verb_forms(db.words[&base_id].as_ref());
+            db.words.insert(id, word);
+        }
+    }
+}
+
+/// Dictionary of the rows of a sheet with the header for the tests
+#[cfg(test)]
+pub(crate) fn database(rows: &[&[&str]]) -> Database {
+    let r = crate::columns::sheet(rows);
+    let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+    read_words(&r, &columns).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::sheet;
+    use crate::conjugation::{Person, Tense};
+
+    #[test]
+    fn homonyms_are_told_apart_by_translation_or_group() {
+        let r = sheet(&[
+            &["Word", "PoS", "Translation", "Group"],
+            &["raten", "v", "советовать", "1"],
+            &["raten", "v", "угадывать", "1"],
+            &["bieten", "v", "предлагать", "Reisen"],
+            &["bieten", "v", "предлагать", "Starke Verben"],
+        ]);
+        let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+        let (db, issues) = read_words(&r, &columns);
+        assert!(issues.is_empty());
+        let lang = Language::default();
+        assert_eq!(db.words["v:raten"].translation(lang), "угадывать");
+        assert_eq!(
+            db.words["v:raten:советовать"].translation(lang),
+            "советовать"
+        );
+        assert_eq!(
+            db.words["v:raten:советовать"].get_id(),
+            "v:raten:советовать"
+        );
+        let bieten = db.words["v:bieten:reisen"].as_ref();
+        assert_eq!(db.groups[bieten.get_group_id()], "Reisen");
+        assert!(db.words.contains_key("v:bieten"));
     }
 
-    db
+    #[test]
+    fn duplicate_word_is_replaced_and_reported() {
+        let r = sheet(&[
+            &["Word", "PoS", "Translation", "Group", "Praeteritum"],
+            &["raten", "v", "советовать", "1", ""],
+            &["heute", "adv", "сегодня", "1", ""],
+            &["raten", "v", "советовать", "1", "riet"],
+        ]);
+        let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+        let (db, issues) = read_words(&r, &columns);
+        assert_eq!(db.words.len(), 2);
+        assert!(db.words["v:raten"].get_verb_praeteritum().is_some());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].row, 2);
+        assert!(issues[0].message.contains("row 4"));
+    }
+
+    #[test]
+    fn separable_verb_takes_the_forms_of_the_base_verb() {
+        let r = sheet(&[
+            &[
+                "Word",
+                "PoS",
+                "Translation",
+                "Group",
+                "3rd single",
+                "Praeteritum",
+            ],
+            &["kommen", "v", "приходить", "1", "kommt", "kam"],
+            &["ankommen", "v", "прибывать", "1", "", ""],
+        ]);
+        let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+        let (db, _) = read_words(&r, &columns);
+        let ankommen = &db.words["v:ankommen"];
+        assert_eq!(
+            ankommen
+                .get_verb_conjugation(Tense::Praeteritum, Person::Du)
+                .as_deref(),
+            Some("kamst an")
+        );
+    }
+
+    #[test]
+    fn translation_to_any_language_is_read() {
+        let r = sheet(&[
+            &["Word", "PoS", "Translation", "Group", "Translation uk"],
+            &["heute", "adv", "сегодня", "1", "сьогодні"],
+        ]);
+        let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+        let (db, issues) = read_words(&r, &columns);
+        assert!(issues.is_empty());
+        let uk = "uk".parse().unwrap();
+        assert_eq!(db.words["adv:heute"].translation(uk), "сьогодні");
+    }
+
+    #[test]
+    fn id_column_keeps_both_meanings() {
+        let r = sheet(&[
+            &["Word", "PoS", "Translation", "Group", "ID"],
+            &["raten", "v", "советовать", "1", ""],
+            &["raten", "v", "угадывать", "1", "raten-2"],
+        ]);
+        let columns = ColumnMap::from_range(&r, &ColumnAliases::default()).unwrap();
+        let (db, issues) = read_words(&r, &columns);
+        assert!(issues.is_empty());
+        assert_eq!(db.words.len(), 2);
+    }
 }
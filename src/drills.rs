@@ -0,0 +1,909 @@
+//! Standard exercises of the game
+
+use std::str::FromStr;
+
+use rand::prelude::*;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+
+use crate::answer::{AnswerSpec, Verdict};
+use crate::conjugation::{Person, Tense};
+use crate::declension::{
+    contract_preposition, decline_phrase, is_article_applicable, ArticleType, Case,
+};
+use crate::exercise::{Exercise, ExerciseRegistry, ResultKind, Skill, Trainer};
+use crate::game_reader::{GameIo, UserInput};
+use crate::language::Language;
+use crate::messages::{fill, text, Msg};
+use crate::words::{
+    capitalize_noun, perfect_answers, Database, Government, NounArticle, PartOfSpeech,
+    PrepositionCase, Word,
+};
+
+const ANSWER_OPTIONS: usize = 4;
+
+/// Form of the verb asked in the verb form exercise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+enum VerbForm {
+    PresentThird,
+    Praeteritum,
+    Perfect,
+}
+
+/// Degree of the adjective asked in the comparison exercise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+enum Degree {
+    Comparative,
+    Superlative,
+}
+
+/// Skills of the exercise, one for every value of the variant enum
+fn variant_skills<T: IntoEnumIterator + Into<&'static str>>(exercise: &str) -> Vec<Skill> {
+    T::iter()
+        .map(|v| Skill::variant(exercise, v.into()))
+        .collect()
+}
+
+/// Value of the variant enum of the exercise skill
+fn parse_variant<T: FromStr>(exercise: &str, skill: &Skill) -> Option<T> {
+    skill.variant_of(exercise)?.parse().ok()
+}
+
+/// Exercises of the game in the menu order, the words are repeated with the translation typing
+pub fn standard() -> ExerciseRegistry {
+    let mut registry = ExerciseRegistry::new(TranslateToDe.name());
+    registry.register(SelectDe);
+    registry.register(TranslateToDe);
+    registry.register(SelectTranslation);
+    registry.register(GuessNounArticle);
+    registry.register(VerbFormRandom);
+    registry.register(NounPlural);
+    registry.register(GuessPrepositionCase);
+    registry.register(AdjectiveComparison);
+    registry.register(AdjectiveDeclension);
+    registry.register(VerbConjugation);
+    registry.register(GuessGovernment);
+    registry.register(TranslateFromDe);
+    registry
+}
+
+/// Select the German word among the words of the same group
+pub struct SelectDe;
+
+impl Exercise for SelectDe {
+    fn name(&self) -> &'static str {
+        "select-de"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseSelectDe, &[&lang])
+    }
+
+    fn is_eligible(&self, _word: &dyn Word) -> bool {
+        true
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Choice
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let lang = trainer.language();
+        let options = fetch_word_options(trainer, word);
+
+        io.prompt(&fill(
+            Msg::SelectGerman,
+            &[&word.translation(lang), &word.pos_str()],
+        ));
+
+        let bullets: Vec<_> = options.iter().map(|w| w.spelling()).collect();
+        let result = match io.choose(&bullets) {
+            UserInput::Answer(a) => options[a].get_id() == word.get_id(),
+            UserInput::InvalidAnswer => false,
+            UserInput::Exit => return None,
+        };
+
+        if result {
+            io.outcome(Verdict::Correct, "");
+        } else {
+            io.outcome(Verdict::Wrong, &fill(Msg::TheWordIs, &[&word.spelling()]));
+        }
+        io.feedback("");
+        Some(result.into())
+    }
+}
+
+/// Type the German word for the translation
+pub struct TranslateToDe;
+
+impl Exercise for TranslateToDe {
+    fn name(&self) -> &'static str {
+        "translate-to-de"
+    }
+
+    // the names with "ru" are kept for the command line compatibility
+    fn aliases(&self) -> &'static [&'static str] {
+        &["translate-ru-de"]
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseTranslateToDe, &[&lang])
+    }
+
+    fn is_eligible(&self, _word: &dyn Word) -> bool {
+        true
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let mut question = fill(
+            Msg::TranslateToGerman,
+            &[&word.translation(trainer.language()), &word.pos_str()],
+        );
+        let help = word.get_help();
+        let government = word.get_government();
+        if !help.is_empty() || government.is_some() {
+            question += &format!(" {}", text(Msg::Help));
+            if !help.is_empty() {
+                question += &format!(" {}", help);
+            }
+            if let Some(government) = government {
+                question += &format!(" ... {}", government);
+            }
+        }
+        io.prompt(&question);
+        let answer = io.read_answer()?;
+        Some(trainer.grade_typed(
+            io,
+            &answer,
+            word.get_answers(),
+            &word.spelling(),
+            Msg::TheWordIs,
+        ))
+    }
+}
+
+/// Select the translation of the German word among the words of the same group
+pub struct SelectTranslation;
+
+impl Exercise for SelectTranslation {
+    fn name(&self) -> &'static str {
+        "select-translation"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["select-ru"]
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseSelectTranslation, &[&lang])
+    }
+
+    fn is_eligible(&self, _word: &dyn Word) -> bool {
+        true
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Choice
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let lang = trainer.language();
+        let options = fetch_word_options(trainer, word);
+
+        io.prompt(&fill(
+            Msg::SelectTranslationTo,
+            &[&lang, &word.spelling(), &word.pos_str()],
+        ));
+
+        let bullets: Vec<_> = options
+            .iter()
+            .map(|w| w.translation(lang).to_owned())
+            .collect();
+        let result = match io.choose(&bullets) {
+            UserInput::Answer(a) => options[a].get_id() == word.get_id(),
+            UserInput::InvalidAnswer => false,
+            UserInput::Exit => return None,
+        };
+
+        if result {
+            io.outcome(Verdict::Correct, "");
+        } else {
+            io.outcome(
+                Verdict::Wrong,
+                &fill(Msg::TheTranslationIs, &[&word.translation(lang)]),
+            );
+        }
+        io.feedback("");
+        Some(result.into())
+    }
+}
+
+/// Select the article of the noun
+pub struct GuessNounArticle;
+
+impl Exercise for GuessNounArticle {
+    fn name(&self) -> &'static str {
+        "guess-noun-article"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseGuessNounArticle, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_pos() == PartOfSpeech::Noun
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Choice
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        io.prompt(&fill(
+            Msg::SelectArticle,
+            &[&capitalize_noun(word.get_word())],
+        ));
+        let bullets: Vec<_> = NounArticle::iter().map(|a| a.answer_bullet_str()).collect();
+        let result = match io.choose(&bullets) {
+            UserInput::Answer(a) => {
+                NounArticle::iter().nth(a).unwrap() == word.get_article().unwrap()
+            }
+            UserInput::InvalidAnswer => false,
+            UserInput::Exit => return None,
+        };
+
+        let answer = format!(
+            "{} - {}",
+            word.spelling(),
+            word.translation(trainer.language())
+        );
+        if result {
+            io.outcome(Verdict::Correct, &answer);
+        } else {
+            io.outcome(Verdict::Wrong, &fill(Msg::TheWordIs, &[&answer]));
+        }
+        io.feedback("");
+        Some(result.into())
+    }
+}
+
+/// Type a form of the verb, the forms are tracked as separate skills
+pub struct VerbFormRandom;
+
+impl Exercise for VerbFormRandom {
+    fn name(&self) -> &'static str {
+        "verb-form-random"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseVerbForm, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        VerbForm::iter().any(|f| has_verb_form(word, f))
+    }
+
+    fn variants(&self) -> Vec<Skill> {
+        variant_skills::<VerbForm>(self.name())
+    }
+
+    fn is_variant_eligible(&self, word: &dyn Word, variant: &Skill) -> bool {
+        parse_variant(self.name(), variant).is_some_and(|form| has_verb_form(word, form))
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let form = match variant.and_then(|v| parse_variant(self.name(), v)) {
+            Some(form) => form,
+            None => VerbForm::iter()
+                .filter(|&f| has_verb_form(word, f))
+                .choose(&mut *trainer.rng())
+                .unwrap(),
+        };
+        io.prompt(&format!(
+            "{} [ {} - {} ]",
+            text(match form {
+                VerbForm::PresentThird => Msg::AddPresent,
+                VerbForm::Praeteritum => Msg::AddPraeteritum,
+                VerbForm::Perfect => Msg::AddPerfect,
+            }),
+            word.get_word(),
+            word.translation(trainer.language())
+        ));
+        // the gap of the separable verb may be typed as well: ruft ... an
+        let answer = io
+            .read_answer()?
+            .split_whitespace()
+            .filter(|s| *s != "...")
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let perfect;
+        let (spec, correct) = match form {
+            VerbForm::PresentThird => {
+                let spec = word.get_verb_present_third().unwrap();
+                (spec, spec.to_string())
+            }
+            VerbForm::Praeteritum => {
+                let spec = word.get_verb_praeteritum().unwrap();
+                (spec, spec.to_string())
+            }
+            VerbForm::Perfect => {
+                perfect = perfect_answers(word).unwrap();
+                (&perfect, word.get_verb_perfect_full().unwrap())
+            }
+        };
+        // a wrong auxiliary verb is a mistake rather than a slip: ist gelesen for hat gelesen
+        let auxiliary = answer
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if form == VerbForm::Perfect
+            && !spec
+                .alternatives()
+                .iter()
+                .any(|a| a.split_whitespace().next() == Some(auxiliary.as_str()))
+        {
+            trainer.show_verdict(
+                io,
+                Verdict::Wrong,
+                &answer,
+                &correct,
+                &correct,
+                Msg::TheWordIs,
+            );
+            return Some(Verdict::Wrong);
+        }
+        Some(trainer.grade_typed(io, &answer, spec, &correct, Msg::TheWordIs))
+    }
+}
+
+/// Type the plural of the noun
+pub struct NounPlural;
+
+impl Exercise for NounPlural {
+    fn name(&self) -> &'static str {
+        "noun-plural"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseNounPlural, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_plural().is_some()
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let plural = word.get_plural().unwrap();
+        io.prompt(&fill(
+            Msg::TypePlural,
+            &[&word.spelling(), &word.translation(trainer.language())],
+        ));
+        let answer = io.read_answer()?;
+
+        let correct = format!("die {}", plural);
+        let answers = AnswerSpec::from_alternatives(vec![correct.clone(), plural.to_owned()]);
+        Some(trainer.grade_typed(io, &answer, &answers, &correct, Msg::ThePluralIs))
+    }
+}
+
+/// Select the case governed by the preposition
+pub struct GuessPrepositionCase;
+
+impl Exercise for GuessPrepositionCase {
+    fn name(&self) -> &'static str {
+        "preposition-case"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExercisePrepositionCase, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_preposition_case().is_some()
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Choice
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let case = word.get_preposition_case().unwrap();
+        io.prompt(&fill(
+            Msg::SelectPrepositionCase,
+            &[&word.get_word(), &word.translation(trainer.language())],
+        ));
+        let bullets: Vec<_> = PrepositionCase::iter()
+            .map(|c| c.answer_bullet_str())
+            .collect();
+        let result = match io.choose(&bullets) {
+            UserInput::Answer(a) => PrepositionCase::iter().nth(a).unwrap() == case,
+            UserInput::InvalidAnswer => false,
+            UserInput::Exit => return None,
+        };
+
+        io.outcome(result.into(), &format!("{} + {}", word.get_word(), case));
+        io.feedback("");
+        Some(result.into())
+    }
+}
+
+/// Type the comparative or the superlative of the adjective
+pub struct AdjectiveComparison;
+
+impl Exercise for AdjectiveComparison {
+    fn name(&self) -> &'static str {
+        "adjective-comparison"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseComparison, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_comparative().is_some()
+    }
+
+    fn variants(&self) -> Vec<Skill> {
+        variant_skills::<Degree>(self.name())
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let degree = match variant.and_then(|v| parse_variant(self.name(), v)) {
+            Some(degree) => degree,
+            None => Degree::iter().choose(&mut *trainer.rng()).unwrap(),
+        };
+        io.prompt(&format!(
+            "{} [ {} - {} ]",
+            text(match degree {
+                Degree::Comparative => Msg::AddComparative,
+                Degree::Superlative => Msg::AddSuperlative,
+            }),
+            word.get_word().trim(),
+            word.translation(trainer.language())
+        ));
+        let answer = io.read_answer()?;
+
+        let correct = match degree {
+            Degree::Comparative => word.get_comparative().unwrap(),
+            Degree::Superlative => word.get_superlative().unwrap(),
+        };
+        // the superlative may be typed without "am"
+        let mut alternatives = vec![correct.to_owned()];
+        alternatives.extend(correct.strip_prefix("am ").map(str::to_owned));
+        let answers = AnswerSpec::from_alternatives(alternatives);
+        Some(trainer.grade_typed(io, &answer, &answers, correct, Msg::TheWordIs))
+    }
+}
+
+/// Decline the adjective with a random noun, case and article: "mit dem alten Hund"
+pub struct AdjectiveDeclension;
+
+impl Exercise for AdjectiveDeclension {
+    fn name(&self) -> &'static str {
+        "adjective-declension"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseDeclension, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_pos() == PartOfSpeech::Adjective
+            && !word.get_word().trim().contains(char::is_whitespace)
+            && word.get_answers().primary() == word.get_word().trim()
+    }
+
+    /// The adjectives are declined with the nouns of the dictionary
+    fn is_available(&self, db: &Database) -> bool {
+        db.words.values().any(|w| self.is_eligible(w.as_ref()))
+            && db.words.values().any(|w| w.get_pos() == PartOfSpeech::Noun)
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let db = trainer.database();
+        let mut rng = trainer.rng();
+        let noun = db
+            .words
+            .values()
+            .filter(|w| w.get_pos() == PartOfSpeech::Noun)
+            .choose(&mut *rng)
+            .unwrap();
+        let noun_article = noun.get_article().unwrap();
+        let case = Case::iter().choose(&mut *rng).unwrap();
+        let article_type = ArticleType::iter()
+            .filter(|&a| is_article_applicable(a, noun_article))
+            .choose(&mut *rng)
+            .unwrap();
+        let preposition = db
+            .words
+            .values()
+            .filter(|w| {
+                w.get_preposition_case()
+                    .is_some_and(|prep| case.is_governed_by(prep))
+            })
+            .choose(&mut *rng)
+            .map(|w| w.get_word().trim());
+
+        let mut question = fill(Msg::TypeDeclension, &[&case, &article_type]);
+        if let Some(prep) = preposition {
+            question += &format!(" {}", fill(Msg::AfterPreposition, &[&prep]));
+        }
+        io.prompt(&format!(
+            "{}: {} + {}",
+            question,
+            word.get_word().trim(),
+            noun.spelling()
+        ));
+        let answer = io.read_answer()?;
+
+        let phrase = decline_phrase(article_type, case, noun_article, word.get_word());
+        let mut accepted = vec![phrase.clone()];
+        if let Some(prep) = preposition {
+            accepted.push(format!("{} {}", prep, phrase));
+            accepted.extend(contract_preposition(prep, &phrase));
+        }
+        let full_phrase = accepted.last().unwrap().clone();
+        let answer = without_noun(&answer, noun.get_word());
+        let answers = AnswerSpec::from_alternatives(accepted);
+        Some(trainer.grade_typed(io, answer, &answers, &full_phrase, Msg::ThePhraseIs))
+    }
+}
+
+/// Answer without the noun typed after the declined adjective in any form: dem alten (Jungen)
+fn without_noun<'a>(answer: &'a str, noun: &str) -> &'a str {
+    let noun = noun.trim().to_lowercase();
+    match answer.trim().rsplit_once(' ') {
+        Some((phrase, last)) if !noun.is_empty() && last.to_lowercase().starts_with(&noun) => {
+            phrase
+        }
+        _ => answer,
+    }
+}
+
+/// Type the verb form of a random person, the tenses are tracked as separate skills
+pub struct VerbConjugation;
+
+impl Exercise for VerbConjugation {
+    fn name(&self) -> &'static str {
+        "verb-conjugation"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseConjugation, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        Tense::iter().any(|tense| word.get_verb_conjugation(tense, Person::Ich).is_some())
+    }
+
+    fn variants(&self) -> Vec<Skill> {
+        variant_skills::<Tense>(self.name())
+    }
+
+    /// The tense is asked only when its form is stored in the dictionary or inherited
+    fn is_variant_eligible(&self, word: &dyn Word, variant: &Skill) -> bool {
+        parse_variant(self.name(), variant)
+            .is_some_and(|tense| word.get_verb_conjugation(tense, Person::Ich).is_some())
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let mut rng = trainer.rng();
+        let tense = match variant.and_then(|v| parse_variant(self.name(), v)) {
+            Some(tense) => tense,
+            None => Tense::iter()
+                .filter(|&tense| word.get_verb_conjugation(tense, Person::Ich).is_some())
+                .choose(&mut *rng)
+                .unwrap(),
+        };
+        let person = Person::iter().choose(&mut *rng).unwrap();
+        drop(rng);
+        io.prompt(&fill(
+            Msg::Conjugate,
+            &[
+                &tense,
+                &person,
+                &word.get_word().trim(),
+                &word.translation(trainer.language()),
+            ],
+        ));
+        let answer = io.read_answer()?;
+
+        let form = word.get_verb_conjugation(tense, person).unwrap();
+        let correct = format!("{} {}", person, form);
+        let answers = AnswerSpec::from_alternatives(vec![correct.clone(), form]);
+        Some(trainer.grade_typed(io, &answer, &answers, &correct, Msg::TheWordIs))
+    }
+}
+
+/// Type the case or the preposition the word is used with
+pub struct GuessGovernment;
+
+impl Exercise for GuessGovernment {
+    fn name(&self) -> &'static str {
+        "government"
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseGovernment, &[&lang])
+    }
+
+    fn is_eligible(&self, word: &dyn Word) -> bool {
+        word.get_government().is_some()
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let government = word.get_government().unwrap();
+        io.prompt(&fill(
+            Msg::TypeGovernment,
+            &[
+                &word.get_word().trim(),
+                &word.translation(trainer.language()),
+            ],
+        ));
+        let answer = government_answer(&io.read_answer()?);
+        let spec = AnswerSpec::from_alternatives(vec![government.to_string()]);
+        let correct = format!("{} {}", word.get_word().trim(), government);
+        Some(trainer.grade_typed(io, &answer, &spec, &correct, Msg::TheWordIs))
+    }
+}
+
+/// Type the translation of the German word
+pub struct TranslateFromDe;
+
+impl Exercise for TranslateFromDe {
+    fn name(&self) -> &'static str {
+        "translate-from-de"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["translate-de-ru"]
+    }
+
+    fn description(&self, lang: Language) -> String {
+        fill(Msg::ExerciseTranslateFromDe, &[&lang])
+    }
+
+    fn is_eligible(&self, _word: &dyn Word) -> bool {
+        true
+    }
+
+    fn result_kind(&self) -> ResultKind {
+        ResultKind::Typed
+    }
+
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        _variant: Option<&Skill>,
+    ) -> Option<Verdict> {
+        let lang = trainer.language();
+        io.prompt(&fill(
+            Msg::TranslateTo,
+            &[&lang, &word.spelling(), &word.pos_str()],
+        ));
+        let answer = io.read_answer()?;
+        let (verdict, expected) = match word.get_translation_answers(lang) {
+            Some(answers) => answers.check_translation(&answer),
+            None => (Verdict::Wrong, ""),
+        };
+        let correct = word.translation(lang);
+        trainer.show_verdict(
+            io,
+            verdict,
+            &answer,
+            expected,
+            correct,
+            Msg::TheTranslationIs,
+        );
+        Some(verdict)
+    }
+}
+
+/// Answer of the government exercise in the form of the dictionary cell: `auf + Akk`,
+/// so that the slips are graded against it
+fn government_answer(answer: &str) -> String {
+    if let Ok(Some(government)) = Government::parse(answer) {
+        return government.to_string();
+    }
+    let answer = answer.replace('+', " ");
+    match answer.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [case] => format!("+ {}", case),
+        [prep, case] => format!("{} + {}", prep.to_lowercase(), case),
+        _ => answer,
+    }
+}
+
+fn has_verb_form(word: &dyn Word, form: VerbForm) -> bool {
+    if word.get_pos() != PartOfSpeech::Verb {
+        return false;
+    }
+    let opt = match form {
+        VerbForm::Praeteritum => word.get_verb_praeteritum(),
+        VerbForm::PresentThird => word.get_verb_present_third(),
+        VerbForm::Perfect => {
+            if word.get_verb_perfect_verb().is_none() {
+                return false;
+            }
+            word.get_verb_perfect()
+        }
+    };
+    opt.is_some_and(|form| !form.is_empty())
+}
+
+/// The word and a few other words of the same group and part of speech in random order
+fn fetch_word_options<'a>(trainer: &'a Trainer, word: &'a dyn Word) -> Vec<&'a dyn Word> {
+    let group_id = word.get_group_id();
+    let pos = word.get_pos();
+    let mut rng = trainer.rng();
+    let others = trainer.database().words.values().filter(|w| {
+        w.get_group_id() == group_id && w.get_pos() == pos && w.get_id() != word.get_id()
+    });
+
+    let mut options: Vec<&dyn Word> = others
+        .choose_multiple(&mut *rng, ANSWER_OPTIONS - 1)
+        .into_iter()
+        .map(|w| w.as_ref())
+        .collect();
+    options.push(word);
+    options.shuffle(&mut *rng);
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Mistake;
+    use crate::dictionary::database;
+    use crate::game_reader::ScriptedReader;
+
+    /// Verdict of the exercise on the word answered with the answer
+    fn grade(
+        exercise: &dyn Exercise,
+        variant: Option<&Skill>,
+        id: &str,
+        answer: &str,
+    ) -> Option<Verdict> {
+        let db = database(&[
+            &[
+                "Word",
+                "PoS",
+                "Translation",
+                "Group",
+                "Perfect",
+                "Perfect Verb",
+                "Rektion",
+            ],
+            &["lesen", "v", "читать", "1", "gelesen", "hat", ""],
+            &["denken", "v", "думать", "1", "gedacht", "hat", "an + Akk"],
+        ]);
+        let trainer = Trainer::new(db, Language::default(), 1);
+        let word = trainer.database().words[id].as_ref();
+        let mut io = ScriptedReader::new([answer]);
+        exercise.run(&trainer, &mut io, word, variant)
+    }
+
+    #[test]
+    fn perfect_with_a_typo_is_a_near_miss() {
+        let perfect = Skill::variant("verb-form-random", "perfect");
+        let grade = |answer| grade(&VerbFormRandom, Some(&perfect), "v:lesen", answer);
+        assert_eq!(grade("hat gelesen"), Some(Verdict::Correct));
+        assert_eq!(grade("hat gelsen"), Some(Verdict::NearMiss(Mistake::Typo)));
+        assert_eq!(grade("ist gelesen"), Some(Verdict::Wrong));
+    }
+
+    #[test]
+    fn government_with_a_typo_is_a_near_miss() {
+        let grade = |answer| grade(&GuessGovernment, None, "v:denken", answer);
+        assert_eq!(grade("an akk"), Some(Verdict::Correct));
+        assert_eq!(grade("an + Akkk"), Some(Verdict::NearMiss(Mistake::Typo)));
+        assert_eq!(grade("auf + Dat"), Some(Verdict::Wrong));
+    }
+
+    #[test]
+    fn translation_with_a_typo_is_a_near_miss() {
+        let grade = |answer| grade(&TranslateFromDe, None, "v:lesen", answer);
+        assert_eq!(grade("Читать"), Some(Verdict::Correct));
+        assert_eq!(grade("читат"), Some(Verdict::NearMiss(Mistake::Typo)));
+        assert_eq!(grade("писать"), Some(Verdict::Wrong));
+    }
+}
@@ -0,0 +1,64 @@
+//! Errors of the game and their exit codes
+
+use std::fmt::Display;
+
+use crate::messages::{fill, Msg};
+pub use crate::progress::ProgressError;
+
+/// Errors of loading the dictionary and playing the game
+#[derive(Debug)]
+pub enum Error {
+    /// The terminal or another file cannot be used
+    Io(std::io::Error),
+    /// The workbook cannot be opened or has no `Words` sheet
+    Workbook(String),
+    /// The header or the `Columns` sheet of the workbook cannot be understood
+    Parse(String),
+    /// The progress file cannot be loaded, backed up or saved
+    Progress(ProgressError),
+    /// No word can be chosen, the argument tells what for
+    EmptyPool(String),
+    /// The dictionary has problems: their number and the workbook
+    Lint(usize, String),
+}
+
+impl Error {
+    /// Exit code of the program, 2 is used for the usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Lint(..) => 1,
+            Self::Io(_) | Self::Workbook(_) => 3,
+            Self::Parse(_) => 4,
+            Self::Progress(_) => 5,
+            Self::EmptyPool(_) => 6,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Workbook(e) | Self::Parse(e) => write!(f, "{}", e),
+            Self::Progress(e) => write!(f, "{}", e),
+            Self::EmptyPool(e) => write!(f, "{}", fill(Msg::NoWordsFor, &[e])),
+            Self::Lint(problems, file) => {
+                write!(f, "{}", fill(Msg::LintProblems, &[problems, file]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ProgressError> for Error {
+    fn from(e: ProgressError) -> Self {
+        Self::Progress(e)
+    }
+}
@@ -1,49 +1,296 @@
-use crate::{game_reader::GameReader, words::*};
-use colored::Colorize;
+//! Exercises, the trainer asking them and the results of the answers
+
+use crate::answer::{self, AnswerSpec, Verdict};
+use crate::drills;
+use crate::error::Error;
+use crate::game_reader::GameIo;
+use crate::language::Language;
+use crate::messages::{fill, text, Msg};
+use crate::progress::{self, Journal, JournalEntry, ProgressError};
+use crate::scheduler::{self, Schedule, Scheduler, SelectionStrategy, NEW_WORDS_PER_SESSION};
+use crate::words::*;
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::{RefCell, RefMut};
+use std::collections::{HashMap, HashSet};
 use std::{cmp::Ordering, vec};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-const ANSWER_OPTIONS: usize = 4;
-
-#[allow(unused)]
-#[derive(EnumIter)]
-pub enum ExerciseType {
-    SelectDe,
-    TranslateRuDe,
-    SelectRu,
-    GuessNounArticle,
-    VerbFormRandom,
+
+const AUTOSAVE_INTERVAL: usize = 5;
+
+/// Particular ability tested by an exercise, tracked separately for each word
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Skill {
+    /// Exercise of the registry by its name
+    Exercise(String),
+    /// Variant of an exercise by the names of the exercise and of the variant,
+    /// e.g. the tense of the conjugation
+    Variant(String, String),
+}
+
+impl Skill {
+    /// Variant of the exercise: `Skill::variant("verb-conjugation", "present")`
+    pub fn variant(exercise: &str, variant: &str) -> Self {
+        Skill::Variant(exercise.to_owned(), variant.to_owned())
+    }
+
+    /// Name of the variant if the skill is a variant of the exercise
+    pub fn variant_of(&self, exercise: &str) -> Option<&str> {
+        match self {
+            Skill::Variant(ex, variant) if ex == exercise => Some(variant),
+            _ => None,
+        }
+    }
+
+    /// Name of the skill in the statistics: `verb-conjugation/present`
+    pub fn name(&self) -> String {
+        match self {
+            Skill::Exercise(name) => name.clone(),
+            Skill::Variant(exercise, variant) => format!("{}/{}", exercise, variant),
+        }
+    }
 }
-#[derive(Debug, Serialize, Deserialize, Default)]
+
+/// How the answer of the exercise is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultKind {
+    /// One of the numbered options is selected, the word is typed again when it is repeated
+    Choice,
+    /// The answer is typed, a correct one finishes the repetition of the word
+    Typed,
+}
+
+/// Drill asking a question about a word, new drills are added to the [`ExerciseRegistry`]
+pub trait Exercise {
+    /// Name used by the `--mode` option and for the skill in the progress file
+    fn name(&self) -> &'static str;
+
+    /// Other names accepted by the `--mode` option
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Text of the mode menu
+    fn description(&self, lang: Language) -> String;
+
+    /// Check if the word has all the data needed for the exercise
+    fn is_eligible(&self, word: &dyn Word) -> bool;
+
+    /// Check if there are words in the database suitable for the exercise
+    fn is_available(&self, db: &Database) -> bool {
+        db.words.values().any(|w| self.is_eligible(w.as_ref()))
+    }
+
+    /// Skills tracked in addition to the exercise, each question trains one of them
+    fn variants(&self) -> Vec<Skill> {
+        vec![]
+    }
+
+    /// Check if the word can be asked in the variant
+    fn is_variant_eligible(&self, word: &dyn Word, _variant: &Skill) -> bool {
+        self.is_eligible(word)
+    }
+
+    /// Selected answers are counted as the recognition of the word, typed ones as the recall
+    fn result_kind(&self) -> ResultKind;
+
+    /// Ask the question about the word and show the outcome, `None` when the player quits.
+    /// A random variant is asked when none is given
+    fn run(
+        &self,
+        trainer: &Trainer,
+        io: &mut dyn GameIo,
+        word: &dyn Word,
+        variant: Option<&Skill>,
+    ) -> Option<Verdict>;
+}
+
+/// Exercises of the game in the menu order
+pub struct ExerciseRegistry {
+    exercises: Vec<Box<dyn Exercise>>,
+    fallback: &'static str,
+}
+
+impl ExerciseRegistry {
+    /// Registry without exercises, the fallback one is used to repeat the words
+    /// and should accept every word
+    pub fn new(fallback: &'static str) -> Self {
+        ExerciseRegistry {
+            exercises: vec![],
+            fallback,
+        }
+    }
+
+    /// Add the exercise to the end of the menu
+    pub fn register(&mut self, exercise: impl Exercise + 'static) {
+        self.exercises.push(Box::new(exercise));
+    }
+
+    /// Find the exercise by its name or alias
+    pub fn get(&self, name: &str) -> Option<&dyn Exercise> {
+        self.iter()
+            .find(|ex| ex.name() == name || ex.aliases().contains(&name))
+    }
+
+    /// Exercises in the order of the menu
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Exercise> {
+        self.exercises.iter().map(|ex| ex.as_ref())
+    }
+
+    /// Exercise used to repeat the mistaken words when the exercise asked first is not typed
+    pub fn fallback(&self) -> Option<&dyn Exercise> {
+        self.get(self.fallback)
+    }
+}
+
+impl Default for ExerciseRegistry {
+    fn default() -> Self {
+        drills::standard()
+    }
+}
+
+/// Numbers of the answers of a single skill
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct SkillResults {
+    correct: usize,
+    wrong: usize,
+    near_miss: usize,
+}
+
+/// Near-misses cost less than wrong answers
+fn score(correct: usize, near_miss: usize, wrong: usize) -> i32 {
+    correct as i32 - near_miss as i32 - (wrong * 2) as i32
+}
+
+impl SkillResults {
+    /// Number of the correct answers
+    pub fn correct(&self) -> usize {
+        self.correct
+    }
+
+    /// Number of the wrong answers
+    pub fn wrong(&self) -> usize {
+        self.wrong
+    }
+
+    /// Number of the answers with a small slip
+    pub fn near_miss(&self) -> usize {
+        self.near_miss
+    }
+
+    /// Count the answer
+    pub fn add(&mut self, verdict: Verdict) {
+        match verdict {
+            Verdict::Correct => self.correct += 1,
+            Verdict::NearMiss(_) => self.near_miss += 1,
+            Verdict::Wrong => self.wrong += 1,
+        }
+    }
+
+    /// Score of the answers, the lower the worse the skill is known
+    pub fn score(&self) -> i32 {
+        score(self.correct, self.near_miss, self.wrong)
+    }
+}
+
+/// Answers and review schedule of a single word
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ExerciseResults {
-    word: String,
+    id: String,
     correct: usize,
     wrong: usize,
+    near_miss: usize,
+    schedule: Schedule,
+    skills: HashMap<Skill, SkillResults>,
 }
 
 impl ExerciseResults {
-    pub fn add(&mut self, correct: bool) {
-        if correct {
-            self.correct += 1;
-        } else {
-            self.wrong += 1;
+    /// Count the answer for the word and the skills and review the word at the time
+    pub fn add(&mut self, verdict: Verdict, skills: &[Skill], time: u64) {
+        match verdict {
+            Verdict::Correct => self.correct += 1,
+            Verdict::NearMiss(_) => self.near_miss += 1,
+            Verdict::Wrong => self.wrong += 1,
         }
+        for skill in skills {
+            self.skills.entry(skill.clone()).or_default().add(verdict);
+        }
+        // a skill failed more often than not makes every correct answer a hard one,
+        // so the word comes back sooner than the words without the weak skill
+        let quality = match verdict {
+            Verdict::Correct if self.has_weak_skill() => 3,
+            Verdict::Correct => 4,
+            Verdict::NearMiss(_) => 3,
+            Verdict::Wrong => 1,
+        };
+        self.schedule.review(quality, time);
+    }
+
+    /// Identity of the word
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Number of the correct answers
+    pub fn correct(&self) -> usize {
+        self.correct
+    }
+
+    /// Number of the wrong answers
+    pub fn wrong(&self) -> usize {
+        self.wrong
+    }
+
+    /// Number of the answers with a small slip
+    pub fn near_miss(&self) -> usize {
+        self.near_miss
+    }
+
+    /// Review schedule of the word
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Answers of the skill of the word
+    pub fn skill(&self, skill: &Skill) -> Option<&SkillResults> {
+        self.skills.get(skill)
+    }
+
+    fn has_weak_skill(&self) -> bool {
+        self.skills.values().any(|s| s.score() < 0)
     }
 
+    /// Score of all answers, the lower the sooner the word is asked
     pub fn score(&self) -> i32 {
-        self.correct as i32 - (self.wrong * 2) as i32
+        score(self.correct, self.near_miss, self.wrong)
     }
 
+    /// Score of the skill, zero if it was not asked
+    pub fn skill_score(&self, skill: &Skill) -> i32 {
+        self.skills
+            .get(skill)
+            .map(|s| s.score())
+            .unwrap_or_default()
+    }
+
+    /// Results of the word which was not asked yet
     pub fn new(s: &str) -> Self {
         Self {
             correct: 0,
             wrong: 0,
-            word: s.to_owned(),
+            near_miss: 0,
+            id: s.to_owned(),
+            schedule: Schedule::default(),
+            skills: HashMap::new(),
+        }
+    }
+
+    /// Results with the numbers of the correct and wrong answers
+    pub fn with_counts(s: &str, correct: usize, wrong: usize) -> Self {
+        Self {
+            correct,
+            wrong,
+            ..Self::new(s)
         }
     }
 }
@@ -62,479 +309,571 @@ impl PartialOrd for ExerciseResults {
 
 impl PartialEq for ExerciseResults {
     fn eq(&self, other: &Self) -> bool {
-        self.word == other.word
+        self.id == other.id
     }
 }
 
 impl Eq for ExerciseResults {}
 
-pub struct Exercise {
+/// Asks the questions about the words of the dictionary
+pub struct Trainer {
     db: Database,
+    lang: Language,
+    /// Every random choice of the session is drawn from it, so a seeded session is repeatable
+    rng: RefCell<StdRng>,
 }
 
-#[derive(Debug, EnumIter)]
-enum VerbFormExercise {
-    PresentThird,
-    Praeteritum,
-    Perfect,
+/// Summary of the progress shown by the `stats` command
+#[derive(Debug, Default)]
+pub struct ResultsStats {
+    /// Number of the words in the dictionary
+    pub words: usize,
+    /// Words which were never asked
+    pub new: usize,
+    /// Words due for review
+    pub due: usize,
+    /// Correct answers of all words
+    pub correct: usize,
+    /// Wrong answers of all words
+    pub wrong: usize,
+    /// Answers with a small slip of all words
+    pub near_miss: usize,
+    /// Answers of every skill of all words
+    pub skills: HashMap<Skill, SkillResults>,
 }
 
+/// Results of all words, selects the next word to learn and saves the progress file
 pub struct GameResults {
     results: Vec<ExerciseResults>,
     results_filename: String,
-    weights: Vec<f32>,
-    rand_dist: Option<WeightedIndex<f32>>,
     training: Vec<String>,
+    strategy: SelectionStrategy,
+    scheduler: Scheduler,
+    last_seq: u64,
+    journal: Option<Journal>,
+    unsaved: usize,
+    save_interval: usize,
+    notices: Vec<String>,
 }
 
 impl GameResults {
-    pub fn new() -> Self {
+    /// Empty results selecting the words with the strategy
+    pub fn new(strategy: SelectionStrategy) -> Self {
         GameResults {
             results: vec![],
             results_filename: String::new(),
-            weights: vec![],
-            rand_dist: None,
             training: vec![],
+            strategy,
+            scheduler: Scheduler::new(NEW_WORDS_PER_SESSION),
+            last_seq: 0,
+            journal: None,
+            unsaved: 0,
+            save_interval: AUTOSAVE_INTERVAL,
+            notices: vec![],
         }
     }
 
+    /// Messages about loading and saving the results which were not shown yet,
+    /// the frontend decides where to show them
+    pub fn take_notices(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+
+    fn notify(&mut self, notice: String) {
+        self.notices.push(notice);
+    }
+
+    /// Words answered wrong in this session
     pub fn get_training_words(&self) -> &Vec<String> {
         &self.training
     }
 
-    pub fn load_results(&mut self, filename: &str) {
+    /// Load the results for the training: the unsaved answers of the journal are saved,
+    /// a corrupt file is moved aside and the journal is opened for the new answers
+    pub fn load_results(&mut self, filename: &str) -> Result<(), Error> {
         let path = std::path::Path::new(filename);
         self.results_filename = filename.to_owned();
         if path.exists() {
-            let f = std::fs::File::open(path).unwrap();
-            let freader = std::io::BufReader::new(f);
-            self.results = bincode::deserialize_from(freader).unwrap();
-            println!("Loaded previous results, {} entries", self.results.len())
+            self.load_progress_file(path)?;
         }
+        let replayed = self.replay_journal(path);
+        match Journal::open_for_append(&progress::journal_path(path)) {
+            Ok(journal) => self.journal = Some(journal),
+            Err(e) => self.notify(fill(Msg::CannotOpenJournal, &[&e])),
+        }
+        if replayed > 0 {
+            self.notify(fill(Msg::RecoveredAnswers, &[&replayed]));
+            self.save_results()?;
+        }
+        Ok(())
     }
 
-    pub fn save_results(&mut self) {
-        let path = std::path::Path::new(&self.results_filename);
-        let f = std::fs::File::create(path).unwrap();
-        let writer = std::io::BufWriter::new(f);
-
-        self.results.sort_unstable();
-
-        bincode::serialize_into(writer, &self.results).unwrap();
+    /// Load the results and the unsaved answers of the journal for the reports,
+    /// no file is created, changed or moved
+    pub fn read_results(&mut self, filename: &str) -> Result<(), Error> {
+        let path = std::path::Path::new(filename);
+        if path.exists() {
+            self.apply_loaded(progress::load(path)?);
+        }
+        self.replay_journal(path);
+        Ok(())
     }
 
-    pub fn update_with_db(&mut self, db: &Database) {
-        for word in db.words.keys() {
-            let new_entry = ExerciseResults::new(word);
-            if !self.results.contains(&new_entry) {
-                self.results.push(new_entry);
+    /// Load the results, unreadable file is moved aside, the game fails if it cannot be kept
+    fn load_progress_file(&mut self, path: &std::path::Path) -> Result<(), Error> {
+        match progress::load(path) {
+            Ok(loaded) => self.apply_loaded(loaded),
+            // a newer or inaccessible file is left alone, only a corrupt one is replaced
+            Err(e @ (ProgressError::Io(_) | ProgressError::UnsupportedVersion(_))) => {
+                return Err(e.into())
+            }
+            Err(e) => {
+                self.notify(fill(Msg::CannotLoadResults, &[&e]));
+                match progress::backup(path) {
+                    Ok(backup) => self.notify(fill(Msg::OldFileSaved, &[&backup.display()])),
+                    Err(e) => {
+                        self.notify(fill(Msg::CannotBackUp, &[&e]));
+                        return Err(ProgressError::from(e).into());
+                    }
+                }
+                self.notify(text(Msg::FreshResults).to_owned());
             }
         }
-        self.results.sort_unstable()
-    }
-
-    pub fn get_top_words(&self, n: usize) -> Vec<String> {
-        self.results
-            .iter()
-            .take(n)
-            .map(|r| r.word.to_owned())
-            .collect()
-    }
-
-    fn select_word_to_learn(&mut self) -> &mut ExerciseResults {
-        let mut rng = rand::thread_rng();
-        let dist = self.rand_dist.as_ref().unwrap();
-        &mut self.results[dist.sample(&mut rng)]
+        Ok(())
+    }
+
+    /// Take the loaded results
+    fn apply_loaded(&mut self, loaded: progress::LoadedProgress) {
+        self.results = loaded.data.results;
+        self.last_seq = loaded.data.last_seq;
+        if let Some(version) = loaded.migrated_from {
+            self.notify(fill(
+                Msg::MigratedFormat,
+                &[&version, &progress::FORMAT_VERSION],
+            ));
+        }
+        self.notify(fill(Msg::LoadedResults, &[&self.results.len()]));
+    }
+
+    /// Apply the answers which were journaled after the last successful save
+    /// and return their number
+    fn replay_journal(&mut self, path: &std::path::Path) -> usize {
+        let journal_path = progress::journal_path(path);
+        let entries = Journal::open_for_replay(&journal_path).unwrap_or_default();
+        let mut replayed = 0usize;
+        for entry in entries {
+            if entry.seq > self.last_seq {
+                self.apply_answer(entry);
+                replayed += 1;
+            }
+        }
+        replayed
     }
 
-    fn select_word_by_cmp<T>(
-        &mut self,
-        db: &Database,
-        cmp: impl Fn(&dyn Word, &T) -> bool,
-        prop: &T,
-    ) -> &mut ExerciseResults {
-        let mut rng = rand::thread_rng();
-        let mut weights = vec![];
-        let mut indices = vec![];
-        for (i, &weight) in self.weights.iter().enumerate() {
-            let word = &self.results[i].word;
-            if let Some(w) = db.words.get(word) {
-                if cmp(w.as_ref(), prop) {
-                    weights.push(weight);
-                    indices.push(i);
-                }
+    /// Write the results to the progress file and clear the journal
+    pub fn save_results(&mut self) -> Result<(), Error> {
+        let path = std::path::Path::new(&self.results_filename);
+        self.results.sort_unstable();
+        progress::save(path, self.last_seq, &self.results)?;
+        self.unsaved = 0;
+        if let Some(journal) = &mut self.journal {
+            if let Err(e) = journal.clear() {
+                self.notify(fill(Msg::CannotClearJournal, &[&e]));
             }
         }
-        let dist = WeightedIndex::new(weights).unwrap();
-        let idx = dist.sample(&mut rng);
-        &mut self.results[indices[idx]]
-    }
+        Ok(())
+    }
+
+    /// Journal the answer, apply it and save the results every few answers
+    pub fn add_answer(&mut self, id: &str, skills: &[Skill], verdict: Verdict) {
+        self.last_seq += 1;
+        let entry = JournalEntry {
+            seq: self.last_seq,
+            time: scheduler::now(),
+            id: id.to_owned(),
+            skills: skills.to_vec(),
+            verdict,
+        };
+        if let Some(journal) = &mut self.journal {
+            if let Err(e) = journal.append(&entry) {
+                self.notify(fill(Msg::CannotWriteJournal, &[&e]));
+                self.journal = None;
+            }
+        }
+        self.apply_answer(entry);
 
-    fn select_word_by_pos(&mut self, db: &Database, pos: PartOfSpeech) -> &mut ExerciseResults {
-        let cmp = |word: &dyn Word, prop: &PartOfSpeech| &word.get_pos() == prop;
-        return self.select_word_by_cmp(db, cmp, &pos);
+        self.unsaved += 1;
+        if self.unsaved >= self.save_interval {
+            // the answers are kept in the journal, so the game goes on
+            if let Err(e) = self.save_results() {
+                self.notify(fill(Msg::CannotSaveResults, &[&e]));
+            }
+        }
     }
 
-    fn select_word_with_verb_form(
-        &mut self,
-        db: &Database,
-        form: &VerbFormExercise,
-    ) -> &mut ExerciseResults {
-        let cmp = |word: &dyn Word, form: &VerbFormExercise| {
-            if word.get_pos() != PartOfSpeech::Verb {
-                return false;
-            }
-            let opt = match *form {
-                VerbFormExercise::Praeteritum => word.get_verb_praeteritum(),
-                VerbFormExercise::PresentThird => word.get_verb_present_third(),
-                VerbFormExercise::Perfect => {
-                    if word.get_verb_perfect_verb().is_none() {
-                        return false;
-                    }
-                    word.get_verb_perfect()
-                }
-            };
-            match opt {
-                None => false,
-                Some(s) if s.is_empty() => false,
-                _ => true,
+    fn apply_answer(&mut self, entry: JournalEntry) {
+        self.last_seq = self.last_seq.max(entry.seq);
+        let idx = match self.results.iter().position(|r| r.id == entry.id) {
+            Some(i) => i,
+            None => {
+                self.results.push(ExerciseResults::new(&entry.id));
+                self.results.len() - 1
             }
         };
-        return self.select_word_by_cmp(db, cmp, form);
+        self.results[idx].add(entry.verdict, &entry.skills, entry.time);
     }
 
-    pub fn update_weights(&mut self) {
-        self.weights.clear();
-        self.results.sort_unstable();
-        let max_score = self.results.last().unwrap().score();
-        let min_score = self.results.first().unwrap().score();
-        self.weights.extend(
-            self.results
-                .iter()
-                .map(|ex| (2 * max_score - min_score - ex.score() + 1) as f32),
-        );
-        self.rand_dist = Some(WeightedIndex::new(&self.weights).unwrap());
-    }
-}
-
-enum UserInput {
-    Answer(usize),
-    InvalidAnswer,
-    Exit,
-}
-
-impl Exercise {
-    pub fn new(db: Database) -> Self {
-        Exercise { db }
+    /// Migrate the results to the word identities of the dictionary and add the new words
+    pub fn update_with_db(&mut self, db: &Database) {
+        self.migrate_word_keys(db);
+        let known: HashSet<String> = self.results.iter().map(|r| r.id.clone()).collect();
+        for id in db.words.keys() {
+            if !known.contains(id) {
+                self.results.push(ExerciseResults::new(id));
+            }
+        }
+        self.results.sort_unstable()
     }
 
-    pub fn exercise_translate_to_de(
-        &self,
-        reader: &mut GameReader,
-        word: &dyn Word,
-    ) -> Option<bool> {
-        print!(
-            "Translate to German: {} ({})",
-            word.translation(),
-            word.pos_str()
-        );
-        let help = word.get_help();
-        if !help.is_empty() {
-            print!(" Help: {}", help);
+    /// Results saved before the word identities were introduced are keyed by the bare word,
+    /// they are copied to every homonym of the word
+    fn migrate_word_keys(&mut self, db: &Database) {
+        let mut known: HashSet<String> = self.results.iter().map(|r| r.id.clone()).collect();
+        let mut migrated = 0usize;
+        let mut results = vec![];
+        for res in std::mem::take(&mut self.results) {
+            if db.words.contains_key(&res.id) || res.id.contains(':') {
+                results.push(res);
+                continue;
+            }
+            let homonyms: Vec<_> = db
+                .words
+                .values()
+                .filter(|w| w.get_word() == res.id && !known.contains(w.get_id()))
+                .collect();
+            if homonyms.is_empty() {
+                results.push(res);
+                continue;
+            }
+            for w in homonyms {
+                known.insert(w.get_id().to_owned());
+                results.push(ExerciseResults {
+                    id: w.get_id().to_owned(),
+                    ..res.clone()
+                });
+            }
+            migrated += 1;
         }
-        println!();
-        let answer = match reader.read_line() {
-            None => return None,
-            Some(s) => s,
-        };
-        let res = word.check_spelling(&answer);
-
-        if res {
-            println!("{} {}", "Correct!".bold().green(), word.spelling());
-        } else {
-            println!(
-                "{} The word is {}",
-                "Incorrect!".bold().red(),
-                word.spelling()
-            );
+        if migrated > 0 {
+            self.notify(fill(Msg::MigratedWordKeys, &[&migrated]));
         }
-        println!();
-        Some(res)
+        self.results = results;
     }
 
-    fn exercise_verb_form(
-        &self,
-        reader: &mut GameReader,
-        word: &dyn Word,
-        form: &VerbFormExercise,
-    ) -> Option<bool> {
-        println!(
-            "{} [ {} - {} ]",
-            match form {
-                VerbFormExercise::PresentThird => "Add verb in present tense: Er ... jetzt",
-                VerbFormExercise::Praeteritum => "Add verb in Präteritum : Er ... einst",
-                VerbFormExercise::Perfect => "Add verb in Perfekt : Er ... ... gestern",
-            },
-            word.get_word(),
-            word.translation()
-        );
-        let answer = match reader.read_line() {
-            None => return None,
-            Some(s) => s,
-        };
+    /// Save the results after the number of answers
+    pub fn set_save_interval(&mut self, answers: usize) {
+        self.save_interval = answers.max(1);
+    }
 
-        let correct = match form {
-            VerbFormExercise::PresentThird => word.get_verb_present_third().unwrap().to_owned(),
-            VerbFormExercise::Praeteritum => word.get_verb_praeteritum().unwrap().to_owned(),
-            VerbFormExercise::Perfect => word.get_verb_perfect_full().unwrap(),
+    /// Summary of the results for the `stats` command
+    pub fn get_stats(&self) -> ResultsStats {
+        let now = scheduler::now();
+        let mut stats = ResultsStats {
+            words: self.results.len(),
+            ..Default::default()
         };
-        let res = match form {
-            VerbFormExercise::PresentThird | VerbFormExercise::Praeteritum => {
-                check_spelling_simple(&answer, &correct)
+        for res in &self.results {
+            stats.correct += res.correct;
+            stats.wrong += res.wrong;
+            stats.near_miss += res.near_miss;
+            if res.schedule.is_new() {
+                stats.new += 1;
+            } else if res.schedule.is_due(now) {
+                stats.due += 1;
+            }
+            for (skill, skill_res) in &res.skills {
+                let entry = stats.skills.entry(skill.clone()).or_default();
+                entry.correct += skill_res.correct;
+                entry.wrong += skill_res.wrong;
+                entry.near_miss += skill_res.near_miss;
             }
-            VerbFormExercise::Perfect => check_spelling_perfect(&answer, word),
-        };
-        if res {
-            println!("{} {}", "Correct!".bold().green(), correct);
-        } else {
-            println!("{} The word is {}", "Incorrect!".bold().red(), correct);
         }
-        println!();
-        Some(res)
+        stats
     }
 
-    pub fn exercise_verb_form_random(
-        &self,
-        reader: &mut GameReader,
-        word: &dyn Word,
-    ) -> Option<bool> {
-        let mut rng = rand::thread_rng();
-        let form = VerbFormExercise::iter().choose(&mut rng).unwrap();
-        self.exercise_verb_form(reader, word, &form)
-    }
-
-    fn fetch_word_options<'a>(&'a self, word: &'a dyn Word) -> Vec<&'a dyn Word> {
-        let group_id = word.get_group_id();
-        let pos = word.get_pos();
-        let mut rng = rand::thread_rng();
-        let candidates: Vec<_> = self
-            .db
-            .words
+    /// Spellings of the first words by score, the results have to be sorted
+    pub fn get_top_words(&self, n: usize, db: &Database) -> Vec<String> {
+        self.results
             .iter()
-            .filter_map(|(_, w)| {
-                if w.get_group_id() == group_id && w.get_pos() == pos {
-                    Some(w)
-                } else {
-                    None
-                }
-            })
+            .filter_map(|r| db.words.get(&r.id))
+            .take(n)
+            .map(|w| w.spelling())
+            .collect()
+    }
+
+    fn schedule_next(&mut self, rng: &mut StdRng, indices: &[usize]) -> Option<usize> {
+        let candidates: Vec<_> = indices
+            .iter()
+            .map(|&i| (i, &self.results[i].schedule))
             .collect();
+        self.scheduler.select(rng, &candidates, scheduler::now())
+    }
+
+    /// Number of the words due for review now
+    pub fn due_count(&self) -> usize {
+        let now = scheduler::now();
+        self.results
+            .iter()
+            .filter(|r| r.schedule.is_due(now))
+            .count()
+    }
 
-        let mut options = HashMap::new();
-        options.insert(word.get_word(), word);
-
-        const MAX_ATTEMPTS: usize = 1000;
-        let mut attempts = 0usize;
-        while options.len() < ANSWER_OPTIONS.min(candidates.len()) {
-            let cand = &***candidates.choose(&mut rng).unwrap();
-            if options.insert(cand.get_word(), cand).is_some() {
-                attempts += 1;
-                if attempts >= MAX_ATTEMPTS {
-                    panic!("Cannot choose answer options");
+    /// Select one of the words accepted by the exercise, the score strategy
+    /// prefers the words with the low score of the skill
+    fn select_word(
+        &mut self,
+        rng: &mut StdRng,
+        db: &Database,
+        eligible: impl Fn(&dyn Word) -> bool,
+        skill: &Skill,
+    ) -> Option<&mut ExerciseResults> {
+        let mut scores = vec![];
+        let mut indices = vec![];
+        for (i, res) in self.results.iter().enumerate() {
+            if let Some(w) = db.words.get(&res.id) {
+                if eligible(w.as_ref()) {
+                    scores.push(res.skill_score(skill));
+                    indices.push(i);
                 }
             }
         }
-
-        let mut opt_vec: Vec<&dyn Word> = options.into_values().collect();
-        opt_vec.shuffle(&mut rng);
-        opt_vec
-    }
-
-    pub fn exercise_select_de(&self, reader: &mut GameReader, word: &dyn Word) -> Option<bool> {
-        let options = self.fetch_word_options(word);
-
-        println!(
-            "Select translation to Deutsch: {} ({})",
-            word.translation(),
-            word.pos_str()
-        );
-
-        let bullets: Vec<_> = options.iter().map(|w| w.spelling()).collect();
-        let result = match print_options_and_guess(&bullets, reader) {
-            UserInput::Answer(a) => options[a].get_word() == word.get_word(),
-            UserInput::InvalidAnswer => false,
-            UserInput::Exit => return None,
-        };
-
-        if result {
-            println!("{}", "Correct!".bold().green());
-            true
-        } else {
-            println!(
-                "{} The word is {}",
-                "Incorrect!".bold().red(),
-                word.spelling()
-            );
-            false
-        };
-        println!();
-        Some(result)
-    }
-
-    pub fn guess_noun_article(&self, reader: &mut GameReader, word: &dyn Word) -> Option<bool> {
-        println!(
-            "Select the correct article for the noun: {}",
-            capitalize_noun(word.get_word())
-        );
-        let bullets: Vec<_> = NounArticle::iter().map(|a| a.answer_bullet_str()).collect();
-        let result = match print_options_and_guess(&bullets, reader) {
-            UserInput::Answer(a) => {
-                NounArticle::iter().nth(a).unwrap() == word.get_article().unwrap()
+        let idx = match self.strategy {
+            SelectionStrategy::Score => {
+                let max_score = scores.iter().copied().max().unwrap_or_default();
+                let min_score = scores.iter().copied().min().unwrap_or_default();
+                let weights = scores
+                    .iter()
+                    .map(|score| (2 * max_score - min_score - score + 1) as f32);
+                let dist = WeightedIndex::new(weights).ok()?;
+                indices[dist.sample(rng)]
             }
-            UserInput::InvalidAnswer => false,
-            UserInput::Exit => return None,
+            SelectionStrategy::SpacedRepetition => self.schedule_next(rng, &indices)?,
         };
+        Some(&mut self.results[idx])
+    }
 
-        if result {
-            print!("{}", "Correct! ".bold().green());
-            true
-        } else {
-            print!("{} The word is ", "Incorrect!".bold().red());
-            false
-        };
-        println!("{} - {}", word.spelling(), word.translation());
-        println!();
-        Some(result)
+    /// Keep the results sorted by the score, the words with the lowest score come first
+    pub fn sort_by_score(&mut self) {
+        self.results.sort_unstable();
     }
+}
 
-    pub fn exercise_select_ru(&self, reader: &mut GameReader, word: &dyn Word) -> Option<bool> {
-        let options = self.fetch_word_options(word);
+impl Trainer {
+    /// Trainer of the dictionary words translated to the language, the seed repeats the session
+    pub fn new(db: Database, lang: Language, seed: u64) -> Self {
+        Trainer {
+            db,
+            lang,
+            rng: RefCell::new(StdRng::seed_from_u64(seed)),
+        }
+    }
 
-        println!(
-            "Select translation to Russian: {} ({})",
-            word.spelling(),
-            word.pos_str()
-        );
+    /// Random generator of the session
+    pub fn rng(&self) -> RefMut<'_, StdRng> {
+        self.rng.borrow_mut()
+    }
 
-        let bullets: Vec<_> = options.iter().map(|w| w.translation().to_owned()).collect();
-        let result = match print_options_and_guess(&bullets, reader) {
-            UserInput::Answer(a) => options[a].get_word() == word.get_word(),
-            UserInput::InvalidAnswer => false,
-            UserInput::Exit => return None,
-        };
+    /// Words of the dictionary
+    pub fn database(&self) -> &Database {
+        &self.db
+    }
 
-        if result {
-            println!("{}", "Correct!".bold().green());
-            true
-        } else {
-            println!(
-                "{} The tranlation is {}",
-                "Incorrect!".bold().red(),
-                word.translation()
-            );
-            false
-        };
-        println!();
-        Some(result)
+    /// Language of the translations
+    pub fn language(&self) -> Language {
+        self.lang
     }
 
+    /// Select the word for the exercise, ask it and record the answer.
+    /// The exercises with variants train a random variant which has suitable words
     pub fn exercise(
         &self,
-        reader: &mut GameReader,
+        io: &mut dyn GameIo,
         results: &mut GameResults,
-        ex_type: &ExerciseType,
-    ) -> Option<bool> {
-        let exercise_result = match ex_type {
-            ExerciseType::VerbFormRandom => {
-                let mut rng = rand::thread_rng();
-                let form = VerbFormExercise::iter().choose(&mut rng).unwrap();
-                results.select_word_with_verb_form(&self.db, &form)
-            }
-            ExerciseType::GuessNounArticle => {
-                results.select_word_by_pos(&self.db, PartOfSpeech::Noun)
+        exercise: &dyn Exercise,
+    ) -> Result<Option<Verdict>, Error> {
+        let mut skills = vec![Skill::Exercise(exercise.name().to_owned())];
+        let mut rng = self.rng();
+        let variant = exercise
+            .variants()
+            .into_iter()
+            .filter(|v| {
+                self.db
+                    .words
+                    .values()
+                    .any(|w| exercise.is_variant_eligible(w.as_ref(), v))
+            })
+            .choose(&mut *rng);
+        let selected = match &variant {
+            Some(v) => results.select_word(
+                &mut rng,
+                &self.db,
+                |w| exercise.is_variant_eligible(w, v),
+                v,
+            ),
+            None => {
+                results.select_word(&mut rng, &self.db, |w| exercise.is_eligible(w), &skills[0])
             }
-            _ => results.select_word_to_learn(),
+        }
+        .map(|r| r.id.clone());
+        skills.extend(variant);
+        // the exercises draw from the session generator as well
+        drop(rng);
+        let word_key = match selected {
+            Some(id) => id,
+            None => return Err(Error::EmptyPool(skills.last().unwrap().name())),
         };
-        let word = match self.db.words.get(&exercise_result.word) {
+        let word = match self.db.words.get(&word_key) {
             Some(w) => &**w,
             None => {
-                return Some(false);
+                return Ok(Some(Verdict::Wrong));
             }
         };
 
-        let result = self.exercise_with_type(reader, word, ex_type)?;
+        let result = match exercise.run(self, io, word, skills.get(1)) {
+            Some(r) => r,
+            None => return Ok(None),
+        };
 
-        exercise_result.add(result);
-        if !result {
-            results.training.push(word.get_word().to_owned());
+        results.add_answer(&word_key, &skills, result);
+        for notice in results.take_notices() {
+            io.feedback(&notice);
+        }
+        if !result.is_correct() {
+            results.training.push(word.get_id().to_owned());
         }
-        Some(result)
+        Ok(Some(result))
     }
 
-    pub fn exercise_with_type(
+    /// Check the typed answer against the accepted ones and show the outcome,
+    /// `correct` is the answer shown to the player
+    pub fn grade_typed(
         &self,
-        reader: &mut GameReader,
-        word: &dyn Word,
-        ex_type: &ExerciseType,
-    ) -> Option<bool> {
-        match ex_type {
-            ExerciseType::TranslateRuDe => self.exercise_translate_to_de(reader, word),
-            ExerciseType::SelectDe => self.exercise_select_de(reader, word),
-            ExerciseType::GuessNounArticle => self.guess_noun_article(reader, word),
-            ExerciseType::SelectRu => self.exercise_select_ru(reader, word),
-            ExerciseType::VerbFormRandom => self.exercise_verb_form_random(reader, word),
+        io: &mut dyn GameIo,
+        answer: &str,
+        spec: &AnswerSpec,
+        correct: &str,
+        the_answer_is: Msg,
+    ) -> Verdict {
+        let (verdict, expected) = spec.check(answer);
+        self.show_verdict(io, verdict, answer, expected, correct, the_answer_is);
+        verdict
+    }
+
+    /// Show the outcome of a typed answer, near-misses are shown with the differing letters
+    /// of the closest accepted answer
+    pub fn show_verdict(
+        &self,
+        io: &mut dyn GameIo,
+        verdict: Verdict,
+        answer: &str,
+        expected: &str,
+        correct: &str,
+        the_answer_is: Msg,
+    ) {
+        match verdict {
+            Verdict::Correct => io.outcome(verdict, correct),
+            Verdict::NearMiss(mistake) => {
+                io.outcome(verdict, &fill(Msg::NearMiss, &[&mistake, &correct]));
+                io.show_diff(&answer::diff_ops(answer.trim(), expected));
+            }
+            Verdict::Wrong => io.outcome(verdict, &fill(the_answer_is, &[&correct])),
         }
+        io.feedback("");
     }
 
-    #[allow(unused)]
-    pub fn exercise_with_random_type(
+    /// Ask the word without recording the answer, used to repeat the mistakes
+    pub fn run(
         &self,
-        reader: &mut GameReader,
+        io: &mut dyn GameIo,
         word: &dyn Word,
-    ) -> Option<bool> {
-        self.exercise_with_type(reader, word, &self.get_random_exercise_type(word))
+        exercise: &dyn Exercise,
+    ) -> Option<Verdict> {
+        exercise.run(self, io, word, None)
     }
 
-    pub fn get_word_from_database(&self, word: &str) -> &dyn Word {
-        self.db.words.get(word).unwrap().as_ref()
-    }
-
-    pub fn get_random_exercise_type(&self, word: &dyn Word) -> ExerciseType {
-        if let PartOfSpeech::Verb = word.get_pos() {
-            if word.get_verb_praeteritum().is_some() {
-                return ExerciseType::VerbFormRandom;
-            }
-        }
-        let mut rng = rand::thread_rng();
-        let ex_type_prelim = ExerciseType::iter().choose(&mut rng).unwrap();
-        match (ex_type_prelim, word.get_pos()) {
-            (ExerciseType::VerbFormRandom, _) => ExerciseType::TranslateRuDe,
-            (ExerciseType::GuessNounArticle, PartOfSpeech::Noun) => ExerciseType::GuessNounArticle,
-            (ExerciseType::GuessNounArticle, _) => ExerciseType::TranslateRuDe,
-            (t, _) => t,
-        }
+    /// Word of the dictionary by its identity
+    pub fn get_word_from_database(&self, id: &str) -> Option<&dyn Word> {
+        self.db.words.get(id).map(|w| w.as_ref())
     }
 }
 
-fn print_options_and_guess(options: &[String], reader: &mut GameReader) -> UserInput {
-    let mut count = 0usize;
-
-    for (i, option) in options.iter().enumerate() {
-        println!("{}) {}", i + 1, option);
-        count += 1;
-    }
-    let input_str = match reader.read_line() {
-        Some(s) => s,
-        None => return UserInput::Exit,
-    };
-    let select: usize = match input_str.parse() {
-        Err(_) => return UserInput::InvalidAnswer,
-        Ok(v) => v,
-    };
-
-    if select < 1 || select > count {
-        UserInput::InvalidAnswer
-    } else {
-        UserInput::Answer(select - 1)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn weak_skill_slows_down_the_schedule() {
+        let form = Skill::variant("verb-form-random", "praeteritum");
+        let translation = Skill::Exercise("translate-to-de".to_owned());
+        let mut weak = ExerciseResults::new("v:gehen");
+        let mut strong = ExerciseResults::new("v:kommen");
+        weak.add(Verdict::Wrong, std::slice::from_ref(&form), 0);
+        strong.add(Verdict::Correct, &[form], 0);
+
+        let mut time = DAY;
+        for _ in 0..4 {
+            weak.add(Verdict::Correct, std::slice::from_ref(&translation), time);
+            strong.add(Verdict::Correct, std::slice::from_ref(&translation), time);
+            time = weak.schedule.due.max(strong.schedule.due);
+        }
+        assert!(weak.schedule.ease < strong.schedule.ease);
+        assert!(weak.schedule.interval_days < strong.schedule.interval_days);
+    }
+
+    #[test]
+    fn newer_progress_file_is_kept() {
+        let path = std::env::temp_dir().join(format!("dw-newer-{}.bin", std::process::id()));
+        let mut bytes = b"DWPF".to_vec();
+        bytes.extend((progress::FORMAT_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        let mut results = GameResults::new(SelectionStrategy::SpacedRepetition);
+        let loaded = results.load_results(path.to_str().unwrap());
+        assert!(matches!(
+            loaded,
+            Err(Error::Progress(ProgressError::UnsupportedVersion(_)))
+        ));
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reading_results_changes_no_files() {
+        let path = std::env::temp_dir().join(format!("dw-read-{}.bin", std::process::id()));
+        let journal = progress::journal_path(&path);
+        std::fs::write(&path, b"DWPF\x01").unwrap();
+        let mut results = GameResults::new(SelectionStrategy::SpacedRepetition);
+        let loaded = results.read_results(path.to_str().unwrap());
+        assert!(matches!(
+            loaded,
+            Err(Error::Progress(ProgressError::Corrupt(_)))
+        ));
+        assert_eq!(std::fs::read(&path).unwrap(), b"DWPF\x01");
+        assert!(!journal.exists());
+        let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+        let siblings = std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(&name))
+            .count();
+        assert_eq!(siblings, 1);
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = std::env::temp_dir().join(format!("dw-none-{}.bin", std::process::id()));
+        let mut results = GameResults::new(SelectionStrategy::SpacedRepetition);
+        results.read_results(missing.to_str().unwrap()).unwrap();
+        assert!(!missing.exists());
+        assert!(!progress::journal_path(&missing).exists());
     }
 }
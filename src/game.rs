@@ -1,97 +1,240 @@
+//! Training session of the selected exercises
+
+use rand::seq::IteratorRandom;
 use rand::Rng;
 
+use crate::error::Error;
 use crate::exercise::*;
-use crate::game_reader::GameReader;
+use crate::game_reader::GameIo;
+use crate::language::Language;
+use crate::messages::{fill, text, Msg};
+use crate::scheduler::SelectionStrategy;
 use crate::words::Database;
 
-impl ExerciseType {
-    pub fn to_string(&self) -> &'static str {
-        match &self {
-            ExerciseType::SelectDe => "1) Select correct word in Deutsch",
-            ExerciseType::TranslateRuDe => "2) Type in word in Deutsch",
-            ExerciseType::SelectRu => "3) Select correct translation to Russian",
-            ExerciseType::GuessNounArticle => "4) Select correct noun atricle",
-            ExerciseType::VerbFormRandom => "5) Type in correct verb form",
-        }
-    }
+/// Settings of the training session
+pub struct GameOptions {
+    /// Number of the questions of every exercise in a round
+    pub exercise_max_cnt: usize,
+    /// Path of the progress file
+    pub progress_file: String,
+    /// Selection of the words to ask
+    pub strategy: SelectionStrategy,
+    /// Save the progress after this number of answers instead of the default
+    pub save_interval: Option<usize>,
+    /// Language of the translations
+    pub language: Language,
+    /// Random seed of the session, a new one is drawn when absent
+    pub seed: Option<u64>,
 }
 
 fn play_game_round(
     exercise_max_cnt: usize,
-    exercise: &Exercise,
-    exercise_types: &Vec<ExerciseType>,
-    game_reader: &mut GameReader,
+    trainer: &Trainer,
+    exercises: &[&dyn Exercise],
+    registry: &ExerciseRegistry,
+    io: &mut dyn GameIo,
     results: &mut GameResults,
-) -> Option<()> {
-    for exercise_type in exercise_types {
+) -> Result<Option<()>, Error> {
+    for exercise in exercises {
         for _ in 0..exercise_max_cnt {
-            exercise.exercise(game_reader, results, exercise_type)?;
-            results.update_weights();
+            if trainer.exercise(io, results, *exercise)?.is_none() {
+                return Ok(None);
+            }
+            results.sort_by_score();
         }
     }
-    repeat_words(results.get_training_words(), game_reader, exercise)
+    Ok(repeat_words(
+        results.get_training_words(),
+        io,
+        trainer,
+        registry,
+    ))
 }
 
+/// Play rounds of the exercises of the registry named in the modes until the player quits,
+/// then save the progress
 pub fn play_game(
-    exercise_max_cnt: usize,
-    db: Database,
-    exercise_types: Vec<ExerciseType>,
-    mut game_reader: GameReader,
-) {
-    let mut results = GameResults::new();
-    results.load_results("exercises.bin");
+    options: &GameOptions,
+    mut db: Database,
+    registry: &ExerciseRegistry,
+    modes: &[&str],
+    io: &mut dyn GameIo,
+) -> Result<(), Error> {
+    let skipped = db.retain_translated(options.language);
+    if skipped > 0 {
+        io.prompt(&fill(Msg::WordsSkipped, &[&skipped, &options.language]));
+    }
+    if db.words.is_empty() {
+        return Err(Error::EmptyPool(text(Msg::PoolGame).to_owned()));
+    }
+    let mut results = GameResults::new(options.strategy);
+    results.load_results(&options.progress_file)?;
+    if let Some(interval) = options.save_interval {
+        results.set_save_interval(interval);
+    }
     results.update_with_db(&db);
-    results.update_weights();
-    let ex = Exercise::new(db);
+    results.sort_by_score();
+    show_notices(io, &mut results);
+    if options.strategy == SelectionStrategy::SpacedRepetition {
+        io.prompt(&fill(Msg::WordsDue, &[&results.due_count()]));
+    }
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let trainer = Trainer::new(db, options.language, seed);
+    let mut exercises: Vec<_> = modes.iter().filter_map(|m| registry.get(m)).collect();
+    exercises.retain(|ex| {
+        let available = ex.is_available(trainer.database());
+        if !available {
+            io.prompt(&fill(Msg::NoWordsForExercise, &[&ex.name()]));
+        }
+        available
+    });
+    if exercises.is_empty() {
+        return Err(Error::EmptyPool(
+            text(Msg::PoolSelectedExercises).to_owned(),
+        ));
+    }
 
-    println!("Type \"exit\" or press Ctrl-C to quit game");
-    println!();
-    loop {
-        if play_game_round(
-            exercise_max_cnt,
-            &ex,
-            &exercise_types,
-            &mut game_reader,
+    io.prompt(&fill(Msg::SessionSeed, &[&seed]));
+    io.prompt(text(Msg::QuitHint));
+    io.prompt("");
+    let played = loop {
+        match play_game_round(
+            options.exercise_max_cnt,
+            &trainer,
+            &exercises,
+            registry,
+            io,
             &mut results,
-        )
-        .is_none()
-        {
-            break;
+        ) {
+            Ok(Some(())) => continue,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e),
         }
+    };
+    // the answers given before the failure are kept
+    let saved = results.save_results();
+    show_notices(io, &mut results);
+    played?;
+    saved?;
+    let top_words = results.get_top_words(5, trainer.database());
+    io.prompt(&fill(Msg::TopWords, &[&format!("{:?}", top_words)]));
+    Ok(())
+}
+
+/// Show the messages of loading and saving the results
+pub fn show_notices(io: &mut dyn GameIo, results: &mut GameResults) {
+    for notice in results.take_notices() {
+        io.prompt(&notice);
     }
-    results.save_results();
-    println!("Top words to learn are {:?}", results.get_top_words(5));
 }
 
-fn repeat_words(words: &Vec<String>, reader: &mut GameReader, exercise: &Exercise) -> Option<()> {
+/// Ask the mistaken words in random available exercises, the word is done
+/// after a correct typed answer and is typed again otherwise
+fn repeat_words(
+    words: &[String],
+    io: &mut dyn GameIo,
+    trainer: &Trainer,
+    registry: &ExerciseRegistry,
+) -> Option<()> {
     if words.is_empty() {
-        println!("Congratulations, all answers are correct!");
+        io.prompt(text(Msg::AllCorrect));
         return Some(());
     }
-    println!("There are {} words to repeat", words.len());
-    println!();
+    io.prompt(&fill(Msg::WordsToRepeat, &[&words.len()]));
+    io.prompt("");
+    let available: Vec<_> = registry
+        .iter()
+        .filter(|ex| ex.is_available(trainer.database()))
+        .collect();
     let mut repeat: Vec<_> = words
         .iter()
-        .map(|w| {
-            let word_ref = exercise.get_word_from_database(w);
-            (word_ref, exercise.get_random_exercise_type(word_ref))
+        .filter_map(|w| trainer.get_word_from_database(w))
+        .filter_map(|word| {
+            let exercise = available
+                .iter()
+                .filter(|ex| ex.is_eligible(word))
+                .choose(&mut *trainer.rng())?;
+            Some((word, *exercise))
         })
         .collect();
-    let mut rng = rand::thread_rng();
-
     while !repeat.is_empty() {
-        let elem = repeat.remove(rng.gen_range(0..repeat.len()));
-        let result = exercise.exercise_with_type(reader, elem.0, &elem.1)?;
-        if result {
-            match elem.1 {
-                ExerciseType::TranslateRuDe | ExerciseType::VerbFormRandom => continue,
-                _ => repeat.push((elem.0, ExerciseType::TranslateRuDe)),
-            }
-        } else {
-            repeat.push((elem.0, ExerciseType::TranslateRuDe));
+        let idx = trainer.rng().gen_range(0..repeat.len());
+        let (word, exercise) = repeat.remove(idx);
+        let result = trainer.run(io, word, exercise)?;
+        if result.is_correct() && exercise.result_kind() == ResultKind::Typed {
+            continue;
+        }
+        if let Some(fallback) = registry.fallback() {
+            repeat.push((word, fallback));
         }
     }
-    println!("All words repeated!");
-    println!();
+    io.prompt(text(Msg::AllRepeated));
+    io.prompt("");
     Some(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::database;
+    use crate::game_reader::ScriptedReader;
+
+    #[test]
+    fn scripted_session_shows_plain_transcript() {
+        let db = || {
+            database(&[
+                &["Word", "PoS", "Translation", "Group"],
+                &["heute", "adv", "сегодня", "1"],
+                &["morgen", "adv", "завтра", "1"],
+            ])
+        };
+        let progress = std::env::temp_dir().join(format!("dw-session-{}.bin", std::process::id()));
+        let options = GameOptions {
+            exercise_max_cnt: 2,
+            progress_file: progress.to_str().unwrap().to_owned(),
+            strategy: SelectionStrategy::SpacedRepetition,
+            save_interval: None,
+            language: Language::default(),
+            seed: Some(7),
+        };
+        let registry = ExerciseRegistry::default();
+        let mut io = ScriptedReader::new(["heute", "morgn", "2"]);
+        play_game(&options, db(), &registry, &["translate-to-de"], &mut io).unwrap();
+        let transcript = [
+            "There are 0 words due for review",
+            "Session seed is 7, run with --seed 7 to repeat it",
+            "Type \"exit\" or press Ctrl-C to quit game",
+            "",
+            "Translate to German: сегодня (adv)",
+            ">> heute",
+            "Correct! heute",
+            "",
+            "Translate to German: завтра (adv)",
+            ">> morgn",
+            "Almost! Looks like typo, the word is morgen",
+            "  Your answer: morgn",
+            "  Expected:    morgen",
+            "",
+            "There are 1 words to repeat",
+            "",
+            "Select translation to Russian: morgen (adv)",
+            "1) сегодня",
+            "2) завтра",
+            ">> 2",
+            "Correct!",
+            "",
+            "Translate to German: завтра (adv)",
+            "Top words to learn are [\"morgen\", \"heute\"]",
+        ];
+        assert_eq!(io.output(), transcript);
+
+        // the notices of loading the saved progress are shown through the frontend too
+        let mut io = ScriptedReader::new(Vec::<String>::new());
+        play_game(&options, db(), &registry, &["translate-to-de"], &mut io).unwrap();
+        assert_eq!(io.output()[0], fill(Msg::LoadedResults, &[&2]));
+        assert!(io.output().iter().all(|line| !line.contains('\x1b')));
+
+        std::fs::remove_file(&progress).unwrap();
+        std::fs::remove_file(crate::progress::journal_path(&progress)).unwrap();
+    }
+}
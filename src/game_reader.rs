@@ -1,35 +1,199 @@
+//! Input and output of the game: the terminal and the scripted frontends
+
+use std::collections::VecDeque;
+
+use colored::{ColoredString, Colorize};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 
+use crate::answer::{diff_sides, DiffOp, Verdict};
+use crate::error::Error;
+use crate::messages::{fill, text, Msg};
+
+/// Answer to the question with numbered options
+pub enum UserInput {
+    /// Index of the selected option
+    Answer(usize),
+    /// Answer which is not one of the options
+    InvalidAnswer,
+    /// Player quits the game
+    Exit,
+}
+
+/// Input and output of the game, the exercises are written against it
+/// so the game can be driven by the terminal or by another frontend
+pub trait GameIo {
+    /// Show the question, menu item or status message
+    fn prompt(&mut self, text: &str);
+
+    /// Read the answer, `None` when the player quits the game
+    fn read_answer(&mut self) -> Option<String>;
+
+    /// Show the outcome of the answer, an empty line separates the exercises
+    fn feedback(&mut self, text: &str);
+
+    /// Show the outcome label followed by the text, e.g. "Correct! der Hund"
+    fn outcome(&mut self, verdict: Verdict, text: &str) {
+        let line = format!("{} {}", outcome_label(verdict), text);
+        self.feedback(line.trim_end());
+    }
+
+    /// Show the answer and the expected spelling aligned letter by letter
+    fn show_diff(&mut self, ops: &[DiffOp]) {
+        let (answer, expected) = diff_sides(ops);
+        self.feedback(&fill(Msg::YourAnswer, &[&answer]));
+        self.feedback(&fill(Msg::Expected, &[&expected]));
+    }
+
+    /// Show the numbered options and read the selected one
+    fn choose(&mut self, options: &[String]) -> UserInput {
+        for (i, option) in options.iter().enumerate() {
+            self.prompt(&format!("{}) {}", i + 1, option));
+        }
+        let input_str = match self.read_answer() {
+            Some(s) => s,
+            None => return UserInput::Exit,
+        };
+        match input_str.parse::<usize>() {
+            Ok(select) if (1..=options.len()).contains(&select) => UserInput::Answer(select - 1),
+            _ => UserInput::InvalidAnswer,
+        }
+    }
+}
+
+/// Label of the answer outcome in the current interface language
+pub fn outcome_label(verdict: Verdict) -> &'static str {
+    text(match verdict {
+        Verdict::Correct => Msg::Correct,
+        Verdict::NearMiss(_) => Msg::Almost,
+        Verdict::Wrong => Msg::Incorrect,
+    })
+}
+
+/// Trim the typed line, `None` for the quit commands
+fn parse_answer(line: &str) -> Option<String> {
+    // the case is kept for the capitalization check of the typed answers
+    let answer = line.trim().to_owned();
+    let command = answer.to_lowercase();
+    if command == "exit" || command == "quit" {
+        return None;
+    }
+    Some(answer)
+}
+
+/// Terminal frontend reading the answers with rustyline
 pub struct GameReader {
     reader: Editor<()>,
 }
 
 impl GameReader {
-    pub fn new() -> Self {
-        GameReader {
-            reader: Editor::<()>::new().unwrap(),
-        }
+    /// Terminal frontend, fails if the terminal cannot be read
+    pub fn new() -> Result<Self, Error> {
+        let reader = Editor::<()>::new().map_err(|e| match e {
+            ReadlineError::Io(e) => Error::Io(e),
+            e => Error::Io(std::io::Error::other(e)),
+        })?;
+        Ok(GameReader { reader })
     }
 
+    /// Read the line after the `>> ` prompt, `None` on quit and on errors
     pub fn read_line(&mut self) -> Option<String> {
         let res = self.reader.readline(">> ");
         match res {
-            Ok(s) => {
-                let answer = s.trim().to_lowercase();
-                if answer == "exit" || answer == "quit" {
-                    return None;
-                }
-                Some(answer)
-            }
+            Ok(s) => parse_answer(&s),
             Err(ReadlineError::Interrupted) => {
                 println!("CTRL-C");
                 None
             }
             _ => {
-                println!("Readline error");
+                println!("{}", text(Msg::ReadlineError));
                 None
             }
         }
     }
 }
+
+impl GameIo for GameReader {
+    fn prompt(&mut self, text: &str) {
+        println!("{}", text);
+    }
+
+    fn read_answer(&mut self) -> Option<String> {
+        self.read_line()
+    }
+
+    fn feedback(&mut self, text: &str) {
+        println!("{}", text);
+    }
+
+    fn outcome(&mut self, verdict: Verdict, text: &str) {
+        let label = outcome_label(verdict).bold();
+        let label = match verdict {
+            Verdict::Correct => label.green(),
+            Verdict::NearMiss(_) => label.yellow(),
+            Verdict::Wrong => label.red(),
+        };
+        println!("{}", format!("{} {}", label, text).trim_end());
+    }
+
+    fn show_diff(&mut self, ops: &[DiffOp]) {
+        let mut answer: Vec<ColoredString> = vec![];
+        let mut expected: Vec<ColoredString> = vec![];
+        for op in ops {
+            match *op {
+                DiffOp::Same(c) => {
+                    answer.push(c.to_string().normal());
+                    expected.push(c.to_string().normal());
+                }
+                DiffOp::Replace(a, b) => {
+                    answer.push(a.to_string().red().bold());
+                    expected.push(b.to_string().green().bold());
+                }
+                DiffOp::Extra(a) => answer.push(a.to_string().red().strikethrough()),
+                DiffOp::Missing(b) => expected.push(b.to_string().green().underline()),
+            }
+        }
+        let join = |v: Vec<ColoredString>| v.iter().map(|s| s.to_string()).collect::<String>();
+        println!("{}", fill(Msg::YourAnswer, &[&join(answer)]));
+        println!("{}", fill(Msg::Expected, &[&join(expected)]));
+    }
+}
+
+/// In-memory frontend answering from a prepared script and recording the output,
+/// the game is quit when the script is over
+#[derive(Default)]
+pub struct ScriptedReader {
+    answers: VecDeque<String>,
+    output: Vec<String>,
+}
+
+impl ScriptedReader {
+    /// Frontend answering with the answers in order
+    pub fn new<S: Into<String>>(answers: impl IntoIterator<Item = S>) -> Self {
+        ScriptedReader {
+            answers: answers.into_iter().map(Into::into).collect(),
+            output: vec![],
+        }
+    }
+
+    /// Lines shown so far, the answers are recorded after the `>> ` prompt
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+}
+
+impl GameIo for ScriptedReader {
+    fn prompt(&mut self, text: &str) {
+        self.output.push(text.to_owned());
+    }
+
+    fn read_answer(&mut self) -> Option<String> {
+        let line = self.answers.pop_front()?;
+        self.output.push(format!(">> {}", line));
+        parse_answer(&line)
+    }
+
+    fn feedback(&mut self, text: &str) {
+        self.output.push(text.to_owned());
+    }
+}
@@ -0,0 +1,58 @@
+//! Languages of the translations
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::messages::{text, Msg};
+
+/// Language of the translations, selected by the code in the translation column header:
+/// two or three lowercase letters, e.g. `en` or `uk`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Language {
+    code: [u8; 3],
+    len: usize,
+}
+
+impl Language {
+    /// Code of the language, e.g. `ru`
+    pub fn code(&self) -> &str {
+        // only ASCII letters are stored
+        std::str::from_utf8(&self.code[..self.len]).unwrap()
+    }
+}
+
+/// Plain `Translation` column of the original workbook
+impl Default for Language {
+    fn default() -> Self {
+        Self {
+            code: *b"ru\0",
+            len: 2,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if !(2..=3).contains(&s.len()) || !s.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(());
+        }
+        let mut code = [0; 3];
+        code[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(Self { code, len: s.len() })
+    }
+}
+
+/// Name of the language in the interface language, the code for the languages without one
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self.code() {
+            "ru" => Msg::LanguageRussian,
+            "en" => Msg::LanguageEnglish,
+            code => return write!(f, "{}", code),
+        };
+        write!(f, "{}", text(msg))
+    }
+}
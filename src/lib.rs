@@ -0,0 +1,32 @@
+//! Vocabulary trainer for German words kept in an Excel workbook.
+//!
+//! The dictionary is loaded with [`dictionary::read_database`] into a [`words::Database`]
+//! of [`words::Word`] objects. The [`exercise::Exercise`] drills of an
+//! [`exercise::ExerciseRegistry`], the standard ones are in [`drills`], ask the questions
+//! about the words. [`exercise::Trainer`] runs them through a [`game_reader::GameIo`] frontend
+//! and [`exercise::GameResults`] keeps the answers, selects the words to learn with the
+//! [`scheduler`] and saves the progress. [`game::play_game`] runs the whole training session.
+//!
+//! The interface language is selected with [`set_locale`], the texts of the [`Msg`] keys are
+//! taken with [`text`] and [`fill`].
+
+#![warn(missing_docs)]
+
+pub mod answer;
+pub(crate) mod columns;
+pub mod conjugation;
+pub mod declension;
+pub mod dictionary;
+pub mod drills;
+pub mod error;
+pub mod exercise;
+pub mod game;
+pub mod game_reader;
+pub mod language;
+pub(crate) mod messages;
+pub(crate) mod progress;
+pub mod scheduler;
+pub(crate) mod validation;
+pub mod words;
+
+pub use messages::{fill, set_locale, text, Locale, Msg};
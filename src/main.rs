@@ -1,25 +1,29 @@
-mod dictionary;
-mod exercise;
-mod game;
-mod game_reader;
-mod words;
-
-use crate::dictionary::*;
-use crate::exercise::ExerciseType;
-use crate::game::*;
-use crate::game_reader::GameReader;
-use strum::IntoEnumIterator;
+mod cli;
 
-const EXERCISE_MAX_COUNT: usize = 10;
+use crate::cli::Command;
+use das_woerterbuch::dictionary::*;
+use das_woerterbuch::error::Error;
+use das_woerterbuch::exercise::{ExerciseRegistry, GameResults};
+use das_woerterbuch::game::*;
+use das_woerterbuch::game_reader::{GameIo, GameReader};
+use das_woerterbuch::language::Language;
+use das_woerterbuch::scheduler::SelectionStrategy;
+use das_woerterbuch::words::Database;
+use das_woerterbuch::{fill, set_locale, text, Msg};
+use strum::IntoEnumIterator;
 
-fn select_excercise_mode(reader: &mut GameReader) -> Vec<ExerciseType> {
-    println!("Select exercise mode:");
-    println!("0) All exercises in series");
-    for ex in ExerciseType::iter() {
-        println!("{}", ex.to_string())
+fn select_excercise_mode(
+    io: &mut dyn GameIo,
+    lang: Language,
+    registry: &ExerciseRegistry,
+) -> Vec<&'static str> {
+    io.prompt(text(Msg::SelectMode));
+    io.prompt(&format!("0) {}", text(Msg::AllExercises)));
+    for (i, ex) in registry.iter().enumerate() {
+        io.prompt(&format!("{}) {}", i + 1, ex.description(lang)))
     }
-    println!("other) Quit game");
-    let select: usize = match reader.read_line() {
+    io.prompt(text(Msg::QuitMenu));
+    let select: usize = match io.read_answer() {
         None => return vec![],
         Some(s) => match s.parse() {
             Err(_) => return vec![],
@@ -27,24 +31,170 @@ fn select_excercise_mode(reader: &mut GameReader) -> Vec<ExerciseType> {
         },
     };
     if select == 0 {
-        ExerciseType::iter().collect()
+        registry.iter().map(|ex| ex.name()).collect()
     } else {
-        match ExerciseType::iter().nth(select - 1) {
-            Some(ex) => vec![ex],
+        match registry.iter().nth(select - 1) {
+            Some(ex) => vec![ex.name()],
             None => vec![],
         }
     }
 }
 
-fn main() {
-    let db = fill_database("woerterbuch.xlsx");
-    let mut game_reader = GameReader::new();
-    let exercise_types = select_excercise_mode(&mut game_reader);
-    if exercise_types.is_empty() {
-        println!("Quit game");
-        return;
+fn select_strategy(io: &mut dyn GameIo) -> SelectionStrategy {
+    io.prompt(text(Msg::SelectStrategy));
+    for (i, strategy) in SelectionStrategy::iter().enumerate() {
+        io.prompt(&format!("{}) {}", i + 1, strategy.description()))
+    }
+    let select: usize = match io.read_answer().map(|s| s.parse()) {
+        Some(Ok(n)) if n > 0 => n,
+        _ => return SelectionStrategy::SpacedRepetition,
+    };
+    SelectionStrategy::iter()
+        .nth(select - 1)
+        .unwrap_or(SelectionStrategy::SpacedRepetition)
+}
+
+fn print_stats(db: &Database, progress_file: &str) -> Result<(), Error> {
+    let mut results = GameResults::new(SelectionStrategy::SpacedRepetition);
+    results.read_results(progress_file)?;
+    results.update_with_db(db);
+    for notice in results.take_notices() {
+        println!("{}", notice);
+    }
+    let stats = results.get_stats();
+
+    println!(
+        "{}",
+        fill(Msg::StatsWords, &[&stats.words, &stats.new, &stats.due])
+    );
+    println!(
+        "{}",
+        fill(
+            Msg::StatsAnswers,
+            &[&stats.correct, &stats.near_miss, &stats.wrong]
+        )
+    );
+    let mut skills: Vec<_> = stats.skills.iter().collect();
+    skills.sort_by_key(|(skill, _)| skill.name());
+    for (skill, res) in skills {
+        println!(
+            "{}",
+            fill(
+                Msg::StatsSkill,
+                &[
+                    &format!("{:<32}", skill.name()),
+                    &format!("{:>5}", res.correct()),
+                    &format!("{:>5}", res.near_miss()),
+                    &format!("{:>5}", res.wrong())
+                ]
+            )
+        );
     }
+    let top_words = results.get_top_words(10, db);
+    println!("{}", fill(Msg::TopWords, &[&format!("{:?}", top_words)]));
+    Ok(())
+}
+
+fn list_words(db: &Database, lang: Language) {
+    let mut words: Vec<_> = db.words.values().collect();
+    words.sort_by_key(|w| (w.get_group_id(), w.get_word().to_lowercase()));
+    for word in words {
+        print!(
+            "{:<24} {:<12} {:<32} [{}]",
+            word.spelling(),
+            word.pos_str(),
+            word.translation(lang),
+            db.groups[word.get_group_id()]
+        );
+        for (column, value) in word.get_extra_columns() {
+            print!(" {}: {}", column, value);
+        }
+        println!();
+    }
+}
 
-    play_game(EXERCISE_MAX_COUNT, db, exercise_types, game_reader);
-    println!("Quit dictionary game");
+/// Load the dictionary for the game, the invalid rows are skipped and only counted
+fn load_dictionary(filename: &str) -> Result<Database, Error> {
+    let (db, issues) = read_database(filename)?;
+    if !issues.is_empty() {
+        println!("{}", fill(Msg::ProblemsFound, &[&issues.len(), &filename]));
+    }
+    Ok(db)
+}
+
+fn lint(filename: &str) -> Result<(), Error> {
+    let (_, issues) = read_database(filename)?;
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    if !issues.is_empty() {
+        return Err(Error::Lint(issues.len(), filename.to_owned()));
+    }
+    println!("{}", fill(Msg::LintClean, &[&filename]));
+    Ok(())
+}
+
+fn train(args: cli::Args) -> Result<(), Error> {
+    let db = load_dictionary(&args.dictionary)?;
+    let mut game_reader = GameReader::new()?;
+    let registry = ExerciseRegistry::default();
+    let (modes, strategy) = match args.modes {
+        Some(modes) => (
+            modes,
+            args.strategy.unwrap_or(SelectionStrategy::SpacedRepetition),
+        ),
+        None => {
+            let modes = select_excercise_mode(&mut game_reader, args.language, &registry);
+            if modes.is_empty() {
+                println!("{}", text(Msg::QuitGame));
+                return Ok(());
+            }
+            let strategy = match args.strategy {
+                Some(s) => s,
+                None => select_strategy(&mut game_reader),
+            };
+            (modes, strategy)
+        }
+    };
+
+    let options = GameOptions {
+        exercise_max_cnt: args.count,
+        progress_file: args.progress,
+        strategy,
+        save_interval: args.save_every,
+        language: args.language,
+        seed: args.seed,
+    };
+    play_game(&options, db, &registry, &modes, &mut game_reader)?;
+    println!("{}", text(Msg::QuitDictionaryGame));
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    set_locale(cli::find_locale(&args));
+    let args = match cli::parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::usage());
+            std::process::exit(2);
+        }
+    };
+
+    let res = match args.command {
+        Command::Train => train(args),
+        Command::Stats => {
+            load_dictionary(&args.dictionary).and_then(|db| print_stats(&db, &args.progress))
+        }
+        Command::List => load_dictionary(&args.dictionary).map(|db| list_words(&db, args.language)),
+        Command::Lint => lint(&args.dictionary),
+        Command::Help => {
+            println!("{}", cli::usage());
+            Ok(())
+        }
+    };
+    if let Err(e) = res {
+        eprintln!("{}", e);
+        std::process::exit(e.exit_code());
+    }
 }
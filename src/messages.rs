@@ -0,0 +1,874 @@
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+
+/// Language of the user interface
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// Russian
+    Ru,
+    /// German
+    De,
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Select the interface language once at startup, English is used until then
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// Keys of the interface messages, `{}` in the text are filled with the arguments in order,
+/// `{0}`, `{1}` refer to the arguments by position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // exercise menu
+    /// `Select exercise mode:`
+    SelectMode,
+    /// `All exercises in series`
+    AllExercises,
+    /// `other) Quit game`
+    QuitMenu,
+    /// `Select correct word in Deutsch`
+    ExerciseSelectDe,
+    /// `Type in word in Deutsch`
+    ExerciseTranslateToDe,
+    /// `Select correct translation to {}`
+    ExerciseSelectTranslation,
+    /// `Select correct noun article`
+    ExerciseGuessNounArticle,
+    /// `Type in correct verb form`
+    ExerciseVerbForm,
+    /// `Type in plural form of noun`
+    ExerciseNounPlural,
+    /// `Select case governed by preposition`
+    ExercisePrepositionCase,
+    /// `Type in comparison form of adjective`
+    ExerciseComparison,
+    /// `Type in article and adjective declined for noun`
+    ExerciseDeclension,
+    /// `Type in conjugated verb`
+    ExerciseConjugation,
+    /// `Type in preposition and case of verb or adjective`
+    ExerciseGovernment,
+    /// `Type in translation to {}`
+    ExerciseTranslateFromDe,
+    /// `Select word selection strategy (Enter for default):`
+    SelectStrategy,
+    /// `Spaced repetition (due words first)`
+    StrategySpacedRepetition,
+    /// `Score weighted (old mode)`
+    StrategyScore,
+    /// `Quit game`
+    QuitGame,
+    /// `Quit dictionary game`
+    QuitDictionaryGame,
+
+    // exercises
+    /// `Correct!`
+    Correct,
+    /// `Incorrect!`
+    Incorrect,
+    /// `Almost!`
+    Almost,
+    /// `Looks like {}, the word is {}`
+    NearMiss,
+    /// `  Your answer: {}`
+    YourAnswer,
+    /// `  Expected:    {}`
+    Expected,
+    /// `The word is {}`
+    TheWordIs,
+    /// `The phrase is {}`
+    ThePhraseIs,
+    /// `The plural is {}`
+    ThePluralIs,
+    /// `The translation is {}`
+    TheTranslationIs,
+    /// `Help:`
+    Help,
+    /// `Translate to German: {} ({})`
+    TranslateToGerman,
+    /// `Translate to {}: {} ({})`
+    TranslateTo,
+    /// `Select translation to Deutsch: {} ({})`
+    SelectGerman,
+    /// `Select translation to {}: {} ({})`
+    SelectTranslationTo,
+    /// `Select the correct article for the noun: {}`
+    SelectArticle,
+    /// `Select the case governed by the preposition: {} ({})`
+    SelectPrepositionCase,
+    /// `Type in plural form: {} ({})`
+    TypePlural,
+    /// `Add verb in present tense: Er ... jetzt`
+    AddPresent,
+    /// `Add verb in Präteritum : Er ... einst`
+    AddPraeteritum,
+    /// `Add verb in Perfekt : Er ... ... gestern`
+    AddPerfect,
+    /// `Add comparative: Er ist ... als ich`
+    AddComparative,
+    /// `Add superlative: Er ist ...`
+    AddSuperlative,
+    /// `Conjugate in {}: {} ... [ {} - {} ]`
+    Conjugate,
+    /// `Type in preposition and case (e.g. "auf + Akk" or "Dat"): {} ... ({})`
+    TypeGovernment,
+    /// `Type in {} {}`
+    TypeDeclension,
+    /// `after "{}"`
+    AfterPreposition,
+
+    // words and answers
+    /// `Russian`
+    LanguageRussian,
+    /// `English`
+    LanguageEnglish,
+    /// `typo`
+    MistakeTypo,
+    /// `missing umlaut`
+    MistakeUmlaut,
+    /// `wrong capitalization`
+    MistakeCapitalization,
+    /// `wrong article`
+    MistakeArticle,
+    /// `with definite article`
+    DefiniteArticle,
+    /// `with indefinite article`
+    IndefiniteArticle,
+    /// `without article`
+    NoArticle,
+    /// `noun`
+    PosNoun,
+    /// `verb`
+    PosVerb,
+    /// `adj`
+    PosAdjective,
+    /// `adv`
+    PosAdverb,
+    /// `preposition`
+    PosPreposition,
+    /// `die (plural)`
+    PluralArticle,
+    /// `two-way preposition (Akkusativ/Dativ)`
+    TwoWayPreposition,
+
+    // game
+    /// `{} words without translation to {} are skipped`
+    WordsSkipped,
+    /// `There are {} words due for review`
+    WordsDue,
+    /// `No words for exercise {}, skipped`
+    NoWordsForExercise,
+    /// `Type "exit" or press Ctrl-C to quit game`
+    QuitHint,
+    /// `Session seed is {0}, run with --seed {0} to repeat it`
+    SessionSeed,
+    /// `Top words to learn are {}`
+    TopWords,
+    /// `Congratulations, all answers are correct!`
+    AllCorrect,
+    /// `There are {} words to repeat`
+    WordsToRepeat,
+    /// `All words repeated!`
+    AllRepeated,
+    /// `Words: {}, new: {}, due for review: {}`
+    StatsWords,
+    /// `Answers: {} correct, {} near-miss, {} wrong`
+    StatsAnswers,
+    /// `  {} {} correct {} near-miss {} wrong`
+    StatsSkill,
+    /// `Readline error`
+    ReadlineError,
+
+    // progress
+    /// `Migrated results from format version {} to {}`
+    MigratedFormat,
+    /// `Loaded previous results, {} entries`
+    LoadedResults,
+    /// `Cannot load previous results: {}`
+    CannotLoadResults,
+    /// `Old file is saved as {}`
+    OldFileSaved,
+    /// `Cannot back up old file: {}`
+    CannotBackUp,
+    /// `Starting with fresh results`
+    FreshResults,
+    /// `Cannot open session journal: {}`
+    CannotOpenJournal,
+    /// `Recovered {} answers from unfinished session`
+    RecoveredAnswers,
+    /// `Cannot save results: {}`
+    CannotSaveResults,
+    /// `Cannot clear session journal: {}`
+    CannotClearJournal,
+    /// `Cannot write session journal: {}`
+    CannotWriteJournal,
+    /// `Migrated results of {} words to word identities`
+    MigratedWordKeys,
+
+    // dictionary and errors
+    /// `Cannot open {}: {}`
+    CannotOpenWorkbook,
+    /// `Cannot read sheet {}: {}`
+    CannotReadSheet,
+    /// `No sheet {} in {}`
+    NoSheet,
+    /// `Missing required columns: {}`
+    MissingColumns,
+    /// `Unknown column {} in alias row {}`
+    UnknownAliasColumn,
+    /// `Found {} problems in {}, run "lint" command for details`
+    ProblemsFound,
+    /// `Found {} problems in {}`
+    LintProblems,
+    /// `No problems found in {}`
+    LintClean,
+    /// `row {}: {}`
+    IssueRow,
+    /// `row {} ({}): {}`
+    IssueRowWord,
+    /// `No words for {}`
+    NoWordsFor,
+    /// `the game`
+    PoolGame,
+    /// `the selected exercises`
+    PoolSelectedExercises,
+    /// `cannot access progress file: {}`
+    ProgressIo,
+    /// `progress file is corrupt: {}`
+    ProgressCorrupt,
+    /// `progress file version {} is newer than supported version {}`
+    ProgressNewer,
+
+    // command line
+    /// Help of the command line: `{0}` dictionary, `{1}` progress file, `{2}` modes, `{3}` count
+    Usage,
+    /// `Unexpected argument {}`
+    UnexpectedArgument,
+    /// `Unknown command {}`
+    UnknownCommand,
+    /// `Option {} requires a value`
+    OptionRequiresValue,
+    /// `Unknown mode {}`
+    UnknownMode,
+    /// `{} expects a positive number, got {}`
+    ExpectsPositiveNumber,
+    /// `{} expects a number, got {}`
+    ExpectsNumber,
+    /// `Invalid language code {}`
+    UnknownLanguage,
+    /// `Unknown interface language {}`
+    UnknownUiLanguage,
+    /// `Unknown strategy {}`
+    UnknownStrategy,
+    /// `Unknown option {}`
+    UnknownOption,
+    /// `empty word`
+    EmptyWord,
+    /// `empty part of speech`
+    EmptyPartOfSpeech,
+    /// `unknown part of speech {}`
+    UnknownPartOfSpeech,
+    /// `empty translation`
+    EmptyTranslation,
+    /// `unknown group: the group is empty`
+    EmptyGroup,
+    /// `unrecognized plural {}`
+    UnrecognizedPlural,
+    /// `perfect participle without auxiliary verb`
+    PerfectWithoutAuxiliary,
+    /// `duplicate word {0}, the one in row {1} is used, add an ID to keep both`
+    DuplicateWord,
+    /// `unknown article {}`
+    UnknownArticle,
+    /// `unknown perfect verb {}`
+    UnknownPerfectVerb,
+    /// `present form {0} does not match the verb {1}`
+    PresentFormMismatch,
+    /// `verb form {0} does not match the verb {1}`
+    VerbFormMismatch,
+    /// `irregular comparative {} needs superlative`
+    ComparativeNeedsSuperlative,
+    /// `unknown preposition case {}`
+    UnknownPrepositionCase,
+    /// `unknown case {} in government`
+    UnknownGovernmentCase,
+    /// `invalid government {}`
+    InvalidGovernment,
+    /// `nested parentheses in {}`
+    NestedParentheses,
+    /// `unbalanced parentheses in {}`
+    UnbalancedParentheses,
+    /// `empty alternative in {}`
+    EmptyAlternative,
+    /// `more than {0} optional parts in {1}`
+    TooManyOptionalParts,
+    /// `empty optional part in {}`
+    EmptyOptionalPart,
+}
+
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::SelectMode => "Select exercise mode:",
+        Msg::AllExercises => "All exercises in series",
+        Msg::QuitMenu => "other) Quit game",
+        Msg::ExerciseSelectDe => "Select correct word in Deutsch",
+        Msg::ExerciseTranslateToDe => "Type in word in Deutsch",
+        Msg::ExerciseSelectTranslation => "Select correct translation to {}",
+        Msg::ExerciseGuessNounArticle => "Select correct noun article",
+        Msg::ExerciseVerbForm => "Type in correct verb form",
+        Msg::ExerciseNounPlural => "Type in plural form of noun",
+        Msg::ExercisePrepositionCase => "Select case governed by preposition",
+        Msg::ExerciseComparison => "Type in comparison form of adjective",
+        Msg::ExerciseDeclension => "Type in article and adjective declined for noun",
+        Msg::ExerciseConjugation => "Type in conjugated verb",
+        Msg::ExerciseGovernment => "Type in preposition and case of verb or adjective",
+        Msg::ExerciseTranslateFromDe => "Type in translation to {}",
+        Msg::SelectStrategy => "Select word selection strategy (Enter for default):",
+        Msg::StrategySpacedRepetition => "Spaced repetition (due words first)",
+        Msg::StrategyScore => "Score weighted (old mode)",
+        Msg::QuitGame => "Quit game",
+        Msg::QuitDictionaryGame => "Quit dictionary game",
+
+        Msg::Correct => "Correct!",
+        Msg::Incorrect => "Incorrect!",
+        Msg::Almost => "Almost!",
+        Msg::NearMiss => "Looks like {}, the word is {}",
+        Msg::YourAnswer => "  Your answer: {}",
+        Msg::Expected => "  Expected:    {}",
+        Msg::TheWordIs => "The word is {}",
+        Msg::ThePhraseIs => "The phrase is {}",
+        Msg::ThePluralIs => "The plural is {}",
+        Msg::TheTranslationIs => "The translation is {}",
+        Msg::Help => "Help:",
+        Msg::TranslateToGerman => "Translate to German: {} ({})",
+        Msg::TranslateTo => "Translate to {}: {} ({})",
+        Msg::SelectGerman => "Select translation to Deutsch: {} ({})",
+        Msg::SelectTranslationTo => "Select translation to {}: {} ({})",
+        Msg::SelectArticle => "Select the correct article for the noun: {}",
+        Msg::SelectPrepositionCase => "Select the case governed by the preposition: {} ({})",
+        Msg::TypePlural => "Type in plural form: {} ({})",
+        Msg::AddPresent => "Add verb in present tense: Er ... jetzt",
+        Msg::AddPraeteritum => "Add verb in Präteritum : Er ... einst",
+        Msg::AddPerfect => "Add verb in Perfekt : Er ... ... gestern",
+        Msg::AddComparative => "Add comparative: Er ist ... als ich",
+        Msg::AddSuperlative => "Add superlative: Er ist ...",
+        Msg::Conjugate => "Conjugate in {}: {} ... [ {} - {} ]",
+        Msg::TypeGovernment => {
+            "Type in preposition and case (e.g. \"auf + Akk\" or \"Dat\"): {} ... ({})"
+        }
+        Msg::TypeDeclension => "Type in {} {}",
+        Msg::AfterPreposition => "after \"{}\"",
+
+        Msg::LanguageRussian => "Russian",
+        Msg::LanguageEnglish => "English",
+        Msg::MistakeTypo => "typo",
+        Msg::MistakeUmlaut => "missing umlaut",
+        Msg::MistakeCapitalization => "wrong capitalization",
+        Msg::MistakeArticle => "wrong article",
+        Msg::DefiniteArticle => "with definite article",
+        Msg::IndefiniteArticle => "with indefinite article",
+        Msg::NoArticle => "without article",
+        Msg::PosNoun => "noun",
+        Msg::PosVerb => "verb",
+        Msg::PosAdjective => "adj",
+        Msg::PosAdverb => "adv",
+        Msg::PosPreposition => "preposition",
+        Msg::PluralArticle => "die (plural)",
+        Msg::TwoWayPreposition => "two-way preposition (Akkusativ/Dativ)",
+
+        Msg::WordsSkipped => "{} words without translation to {} are skipped",
+        Msg::WordsDue => "There are {} words due for review",
+        Msg::NoWordsForExercise => "No words for exercise {}, skipped",
+        Msg::QuitHint => "Type \"exit\" or press Ctrl-C to quit game",
+        Msg::SessionSeed => "Session seed is {0}, run with --seed {0} to repeat it",
+        Msg::TopWords => "Top words to learn are {}",
+        Msg::AllCorrect => "Congratulations, all answers are correct!",
+        Msg::WordsToRepeat => "There are {} words to repeat",
+        Msg::AllRepeated => "All words repeated!",
+        Msg::StatsWords => "Words: {}, new: {}, due for review: {}",
+        Msg::StatsAnswers => "Answers: {} correct, {} near-miss, {} wrong",
+        Msg::StatsSkill => "  {} {} correct {} near-miss {} wrong",
+        Msg::ReadlineError => "Readline error",
+
+        Msg::MigratedFormat => "Migrated results from format version {} to {}",
+        Msg::LoadedResults => "Loaded previous results, {} entries",
+        Msg::CannotLoadResults => "Cannot load previous results: {}",
+        Msg::OldFileSaved => "Old file is saved as {}",
+        Msg::CannotBackUp => "Cannot back up old file: {}",
+        Msg::FreshResults => "Starting with fresh results",
+        Msg::CannotOpenJournal => "Cannot open session journal: {}",
+        Msg::RecoveredAnswers => "Recovered {} answers from unfinished session",
+        Msg::CannotSaveResults => "Cannot save results: {}",
+        Msg::CannotClearJournal => "Cannot clear session journal: {}",
+        Msg::CannotWriteJournal => "Cannot write session journal: {}",
+        Msg::MigratedWordKeys => "Migrated results of {} words to word identities",
+
+        Msg::CannotOpenWorkbook => "Cannot open {}: {}",
+        Msg::CannotReadSheet => "Cannot read sheet {}: {}",
+        Msg::NoSheet => "No sheet {} in {}",
+        Msg::MissingColumns => "Missing required columns: {}",
+        Msg::UnknownAliasColumn => "Unknown column {} in alias row {}",
+        Msg::ProblemsFound => "Found {} problems in {}, run \"lint\" command for details",
+        Msg::LintProblems => "Found {} problems in {}",
+        Msg::LintClean => "No problems found in {}",
+        Msg::IssueRow => "row {}: {}",
+        Msg::IssueRowWord => "row {} ({}): {}",
+        Msg::NoWordsFor => "No words for {}",
+        Msg::PoolGame => "the game",
+        Msg::PoolSelectedExercises => "the selected exercises",
+        Msg::ProgressIo => "cannot access progress file: {}",
+        Msg::ProgressCorrupt => "progress file is corrupt: {}",
+        Msg::ProgressNewer => "progress file version {} is newer than supported version {}",
+
+        Msg::Usage => {
+            "Usage: das_woerterbuch [COMMAND] [OPTIONS]
+
+Commands:
+  train    Play the exercises (default)
+  stats    Show learning statistics
+  list     List the words of the dictionary
+  lint     Check the dictionary for invalid rows
+  help     Show this message
+
+Options:
+  --dict <PATH>         Dictionary workbook [default: {0}]
+  --progress <PATH>     Progress file [default: {1}]
+  --mode <MODE>         Exercise mode, can be repeated or comma separated:
+{2}
+  --count <N>           Number of exercises of each type in a round [default: {3}]
+  --strategy <NAME>     Word selection: spaced-repetition or score
+  --save-every <N>      Save progress every N answers
+  --seed <N>            Seed of the session, repeats the same questions
+  --language <CODE>     Language code of the translation column, e.g. en [default: ru]
+  --ui <CODE>           Interface language: en, ru or de [default: en]"
+        }
+        Msg::UnexpectedArgument => "Unexpected argument {}",
+        Msg::UnknownCommand => "Unknown command {}",
+        Msg::OptionRequiresValue => "Option {} requires a value",
+        Msg::UnknownMode => "Unknown mode {}",
+        Msg::ExpectsPositiveNumber => "{} expects a positive number, got {}",
+        Msg::ExpectsNumber => "{} expects a number, got {}",
+        Msg::UnknownLanguage => "Invalid language code {}",
+        Msg::UnknownUiLanguage => "Unknown interface language {}",
+        Msg::UnknownStrategy => "Unknown strategy {}",
+        Msg::UnknownOption => "Unknown option {}",
+        Msg::EmptyWord => "empty word",
+        Msg::EmptyPartOfSpeech => "empty part of speech",
+        Msg::UnknownPartOfSpeech => "unknown part of speech {}",
+        Msg::EmptyTranslation => "empty translation",
+        Msg::EmptyGroup => "unknown group: the group is empty",
+        Msg::UnrecognizedPlural => "unrecognized plural {}",
+        Msg::PerfectWithoutAuxiliary => "perfect participle without auxiliary verb",
+        Msg::DuplicateWord => {
+            "duplicate word {0}, the one in row {1} is used, add an ID to keep both"
+        }
+        Msg::UnknownArticle => "unknown article {}",
+        Msg::UnknownPerfectVerb => "unknown perfect verb {}",
+        Msg::PresentFormMismatch => "present form {0} does not match the verb {1}",
+        Msg::VerbFormMismatch => "verb form {0} does not match the verb {1}",
+        Msg::ComparativeNeedsSuperlative => "irregular comparative {} needs superlative",
+        Msg::UnknownPrepositionCase => "unknown preposition case {}",
+        Msg::UnknownGovernmentCase => "unknown case {} in government",
+        Msg::InvalidGovernment => "invalid government {}",
+        Msg::NestedParentheses => "nested parentheses in {}",
+        Msg::UnbalancedParentheses => "unbalanced parentheses in {}",
+        Msg::EmptyAlternative => "empty alternative in {}",
+        Msg::TooManyOptionalParts => "more than {0} optional parts in {1}",
+        Msg::EmptyOptionalPart => "empty optional part in {}",
+    }
+}
+
+fn russian(msg: Msg) -> &'static str {
+    match msg {
+        Msg::SelectMode => "Выберите режим упражнений:",
+        Msg::AllExercises => "Все упражнения подряд",
+        Msg::QuitMenu => "другое) Выйти из игры",
+        Msg::ExerciseSelectDe => "Выберите правильное слово на немецком",
+        Msg::ExerciseTranslateToDe => "Введите слово на немецком",
+        Msg::ExerciseSelectTranslation => "Выберите правильный перевод на {}",
+        Msg::ExerciseGuessNounArticle => "Выберите правильный артикль существительного",
+        Msg::ExerciseVerbForm => "Введите правильную форму глагола",
+        Msg::ExerciseNounPlural => "Введите множественное число существительного",
+        Msg::ExercisePrepositionCase => "Выберите падеж после предлога",
+        Msg::ExerciseComparison => "Введите степень сравнения прилагательного",
+        Msg::ExerciseDeclension => "Введите артикль и прилагательное в падеже существительного",
+        Msg::ExerciseConjugation => "Введите спрягаемую форму глагола",
+        Msg::ExerciseGovernment => "Введите предлог и падеж для глагола или прилагательного",
+        Msg::ExerciseTranslateFromDe => "Введите перевод на {}",
+        Msg::SelectStrategy => "Выберите стратегию выбора слов (Enter — по умолчанию):",
+        Msg::StrategySpacedRepetition => "Интервальное повторение (сначала слова к повторению)",
+        Msg::StrategyScore => "По набранным очкам (старый режим)",
+        Msg::QuitGame => "Выход из игры",
+        Msg::QuitDictionaryGame => "Выход из словарной игры",
+
+        Msg::Correct => "Верно!",
+        Msg::Incorrect => "Неверно!",
+        Msg::Almost => "Почти!",
+        Msg::NearMiss => "Похоже на ошибку: {}. Правильно: {}",
+        Msg::YourAnswer => "  Ваш ответ:  {}",
+        Msg::Expected => "  Ожидалось:  {}",
+        Msg::TheWordIs => "Правильное слово: {}",
+        Msg::ThePhraseIs => "Правильная фраза: {}",
+        Msg::ThePluralIs => "Множественное число: {}",
+        Msg::TheTranslationIs => "Перевод: {}",
+        Msg::Help => "Подсказка:",
+        Msg::TranslateToGerman => "Переведите на немецкий: {} ({})",
+        Msg::TranslateTo => "Переведите на {}: {} ({})",
+        Msg::SelectGerman => "Выберите перевод на немецкий: {} ({})",
+        Msg::SelectTranslationTo => "Выберите перевод на {}: {} ({})",
+        Msg::SelectArticle => "Выберите правильный артикль существительного: {}",
+        Msg::SelectPrepositionCase => "Выберите падеж, которым управляет предлог: {} ({})",
+        Msg::TypePlural => "Введите множественное число: {} ({})",
+        Msg::AddPresent => "Вставьте глагол в настоящем времени: Er ... jetzt",
+        Msg::AddPraeteritum => "Вставьте глагол в Präteritum: Er ... einst",
+        Msg::AddPerfect => "Вставьте глагол в Perfekt: Er ... ... gestern",
+        Msg::AddComparative => "Вставьте сравнительную степень: Er ist ... als ich",
+        Msg::AddSuperlative => "Вставьте превосходную степень: Er ist ...",
+        Msg::Conjugate => "Проспрягайте ({}): {} ... [ {} - {} ]",
+        Msg::TypeGovernment => {
+            "Введите предлог и падеж (например, \"auf + Akk\" или \"Dat\"): {} ... ({})"
+        }
+        Msg::TypeDeclension => "Введите в падеже {} {}",
+        Msg::AfterPreposition => "после \"{}\"",
+
+        Msg::LanguageRussian => "русский",
+        Msg::LanguageEnglish => "английский",
+        Msg::MistakeTypo => "опечатка",
+        Msg::MistakeUmlaut => "пропущен умлаут",
+        Msg::MistakeCapitalization => "неверный регистр букв",
+        Msg::MistakeArticle => "неверный артикль",
+        Msg::DefiniteArticle => "с определённым артиклем",
+        Msg::IndefiniteArticle => "с неопределённым артиклем",
+        Msg::NoArticle => "без артикля",
+        Msg::PosNoun => "сущ.",
+        Msg::PosVerb => "глаг.",
+        Msg::PosAdjective => "прил.",
+        Msg::PosAdverb => "нареч.",
+        Msg::PosPreposition => "предлог",
+        Msg::PluralArticle => "die (мн. ч.)",
+        Msg::TwoWayPreposition => "предлог двойного управления (Akkusativ/Dativ)",
+
+        Msg::WordsSkipped => "Пропущено слов без перевода на {1}: {0}",
+        Msg::WordsDue => "Слов к повторению: {}",
+        Msg::NoWordsForExercise => "Нет слов для упражнения {}, пропущено",
+        Msg::QuitHint => "Введите \"exit\" или нажмите Ctrl-C для выхода из игры",
+        Msg::SessionSeed => "Номер сессии {0}, запустите с --seed {0}, чтобы повторить её",
+        Msg::TopWords => "Слова, которые стоит подучить: {}",
+        Msg::AllCorrect => "Поздравляем, все ответы верные!",
+        Msg::WordsToRepeat => "Слов для повторения: {}",
+        Msg::AllRepeated => "Все слова повторены!",
+        Msg::StatsWords => "Слов: {}, новых: {}, к повторению: {}",
+        Msg::StatsAnswers => "Ответов: верных {}, почти верных {}, неверных {}",
+        Msg::StatsSkill => "  {} {} верно {} почти {} неверно",
+        Msg::ReadlineError => "Ошибка чтения ввода",
+
+        Msg::MigratedFormat => "Результаты перенесены из формата версии {} в {}",
+        Msg::LoadedResults => "Загружены прежние результаты, записей: {}",
+        Msg::CannotLoadResults => "Не удалось загрузить прежние результаты: {}",
+        Msg::OldFileSaved => "Старый файл сохранён как {}",
+        Msg::CannotBackUp => "Не удалось сохранить копию старого файла: {}",
+        Msg::FreshResults => "Начинаем с чистыми результатами",
+        Msg::CannotOpenJournal => "Не удалось открыть журнал сессии: {}",
+        Msg::RecoveredAnswers => "Восстановлено ответов из незавершённой сессии: {}",
+        Msg::CannotSaveResults => "Не удалось сохранить результаты: {}",
+        Msg::CannotClearJournal => "Не удалось очистить журнал сессии: {}",
+        Msg::CannotWriteJournal => "Не удалось записать журнал сессии: {}",
+        Msg::MigratedWordKeys => "Результаты {} слов перенесены на идентификаторы слов",
+
+        Msg::CannotOpenWorkbook => "Не удалось открыть {}: {}",
+        Msg::CannotReadSheet => "Не удалось прочитать лист {}: {}",
+        Msg::NoSheet => "Нет листа {} в {}",
+        Msg::MissingColumns => "Нет обязательных столбцов: {}",
+        Msg::UnknownAliasColumn => "Неизвестный столбец {} в строке псевдонимов {}",
+        Msg::ProblemsFound => "Найдено проблем в {1}: {0}, подробности покажет команда \"lint\"",
+        Msg::LintProblems => "Найдено проблем в {1}: {0}",
+        Msg::LintClean => "Проблем в {} не найдено",
+        Msg::IssueRow => "строка {}: {}",
+        Msg::IssueRowWord => "строка {} ({}): {}",
+        Msg::NoWordsFor => "Нет слов для {}",
+        Msg::PoolGame => "игры",
+        Msg::PoolSelectedExercises => "выбранных упражнений",
+        Msg::ProgressIo => "нет доступа к файлу прогресса: {}",
+        Msg::ProgressCorrupt => "файл прогресса повреждён: {}",
+        Msg::ProgressNewer => "версия файла прогресса {} новее поддерживаемой версии {}",
+
+        Msg::Usage => {
+            "Использование: das_woerterbuch [КОМАНДА] [ПАРАМЕТРЫ]
+
+Команды:
+  train    Упражнения (по умолчанию)
+  stats    Статистика обучения
+  list     Список слов словаря
+  lint     Проверка словаря на ошибки
+  help     Эта справка
+
+Параметры:
+  --dict <ПУТЬ>         Файл словаря [по умолчанию: {0}]
+  --progress <ПУТЬ>     Файл прогресса [по умолчанию: {1}]
+  --mode <РЕЖИМ>        Режим упражнений, можно повторять или перечислять через запятую:
+{2}
+  --count <N>           Число упражнений каждого типа в раунде [по умолчанию: {3}]
+  --strategy <ИМЯ>      Выбор слов: spaced-repetition или score
+  --save-every <N>      Сохранять прогресс каждые N ответов
+  --seed <N>            Зерно сессии, повторяет те же вопросы
+  --language <КОД>      Код языка столбца перевода, например en [по умолчанию: ru]
+  --ui <КОД>            Язык интерфейса: en, ru или de [по умолчанию: en]"
+        }
+        Msg::UnexpectedArgument => "Лишний аргумент {}",
+        Msg::UnknownCommand => "Неизвестная команда {}",
+        Msg::OptionRequiresValue => "Параметру {} нужно значение",
+        Msg::UnknownMode => "Неизвестный режим {}",
+        Msg::ExpectsPositiveNumber => "{} ожидает положительное число, получено {}",
+        Msg::ExpectsNumber => "{} ожидает число, получено {}",
+        Msg::UnknownLanguage => "Неверный код языка {}",
+        Msg::UnknownUiLanguage => "Неизвестный язык интерфейса {}",
+        Msg::UnknownStrategy => "Неизвестная стратегия {}",
+        Msg::UnknownOption => "Неизвестный параметр {}",
+        Msg::EmptyWord => "пустое слово",
+        Msg::EmptyPartOfSpeech => "пустая часть речи",
+        Msg::UnknownPartOfSpeech => "неизвестная часть речи {}",
+        Msg::EmptyTranslation => "пустой перевод",
+        Msg::EmptyGroup => "неизвестная группа: группа пуста",
+        Msg::UnrecognizedPlural => "нераспознанное множественное число {}",
+        Msg::PerfectWithoutAuxiliary => "причастие прошедшего времени без вспомогательного глагола",
+        Msg::DuplicateWord => {
+            "повтор слова {0}, используется слово из строки {1}, добавьте ID, чтобы оставить оба"
+        }
+        Msg::UnknownArticle => "неизвестный артикль {}",
+        Msg::UnknownPerfectVerb => "неизвестный вспомогательный глагол {}",
+        Msg::PresentFormMismatch => "форма настоящего времени {0} не подходит к глаголу {1}",
+        Msg::VerbFormMismatch => "форма {0} не подходит к глаголу {1}",
+        Msg::ComparativeNeedsSuperlative => {
+            "неправильной сравнительной степени {} нужна превосходная"
+        }
+        Msg::UnknownPrepositionCase => "неизвестный падеж предлога {}",
+        Msg::UnknownGovernmentCase => "неизвестный падеж {} в управлении",
+        Msg::InvalidGovernment => "неверное управление {}",
+        Msg::NestedParentheses => "вложенные скобки в {}",
+        Msg::UnbalancedParentheses => "непарные скобки в {}",
+        Msg::EmptyAlternative => "пустой вариант в {}",
+        Msg::TooManyOptionalParts => "больше {0} необязательных частей в {1}",
+        Msg::EmptyOptionalPart => "пустая необязательная часть в {}",
+    }
+}
+
+fn german(msg: Msg) -> &'static str {
+    match msg {
+        Msg::SelectMode => "Wähle den Übungsmodus:",
+        Msg::AllExercises => "Alle Übungen nacheinander",
+        Msg::QuitMenu => "sonst) Spiel beenden",
+        Msg::ExerciseSelectDe => "Richtiges deutsches Wort wählen",
+        Msg::ExerciseTranslateToDe => "Deutsches Wort eintippen",
+        Msg::ExerciseSelectTranslation => "Richtige Übersetzung wählen ({})",
+        Msg::ExerciseGuessNounArticle => "Richtigen Artikel des Nomens wählen",
+        Msg::ExerciseVerbForm => "Richtige Verbform eintippen",
+        Msg::ExerciseNounPlural => "Pluralform des Nomens eintippen",
+        Msg::ExercisePrepositionCase => "Kasus nach der Präposition wählen",
+        Msg::ExerciseComparison => "Steigerungsform des Adjektivs eintippen",
+        Msg::ExerciseDeclension => "Artikel und dekliniertes Adjektiv zum Nomen eintippen",
+        Msg::ExerciseConjugation => "Konjugiertes Verb eintippen",
+        Msg::ExerciseGovernment => "Präposition und Kasus von Verb oder Adjektiv eintippen",
+        Msg::ExerciseTranslateFromDe => "Übersetzung eintippen ({})",
+        Msg::SelectStrategy => "Wähle die Strategie der Wortauswahl (Enter für Standard):",
+        Msg::StrategySpacedRepetition => "Verteilte Wiederholung (fällige Wörter zuerst)",
+        Msg::StrategyScore => "Nach Punktzahl gewichtet (alter Modus)",
+        Msg::QuitGame => "Spiel beendet",
+        Msg::QuitDictionaryGame => "Wörterbuchspiel beendet",
+
+        Msg::Correct => "Richtig!",
+        Msg::Incorrect => "Falsch!",
+        Msg::Almost => "Fast!",
+        Msg::NearMiss => "Kleiner Fehler: {}. Richtig ist {}",
+        Msg::YourAnswer => "  Deine Antwort: {}",
+        Msg::Expected => "  Erwartet:      {}",
+        Msg::TheWordIs => "Das Wort ist {}",
+        Msg::ThePhraseIs => "Die Phrase ist {}",
+        Msg::ThePluralIs => "Der Plural ist {}",
+        Msg::TheTranslationIs => "Die Übersetzung ist {}",
+        Msg::Help => "Hinweis:",
+        Msg::TranslateToGerman => "Übersetze ins Deutsche: {} ({})",
+        Msg::TranslateTo => "Übersetze ({}): {} ({})",
+        Msg::SelectGerman => "Wähle das deutsche Wort: {} ({})",
+        Msg::SelectTranslationTo => "Wähle die Übersetzung ({}): {} ({})",
+        Msg::SelectArticle => "Wähle den richtigen Artikel für das Nomen: {}",
+        Msg::SelectPrepositionCase => "Wähle den Kasus nach der Präposition: {} ({})",
+        Msg::TypePlural => "Gib die Pluralform ein: {} ({})",
+        Msg::AddPresent => "Setze das Verb im Präsens ein: Er ... jetzt",
+        Msg::AddPraeteritum => "Setze das Verb im Präteritum ein: Er ... einst",
+        Msg::AddPerfect => "Setze das Verb im Perfekt ein: Er ... ... gestern",
+        Msg::AddComparative => "Setze den Komparativ ein: Er ist ... als ich",
+        Msg::AddSuperlative => "Setze den Superlativ ein: Er ist ...",
+        Msg::Conjugate => "Konjugiere im {}: {} ... [ {} - {} ]",
+        Msg::TypeGovernment => {
+            "Gib Präposition und Kasus ein (z. B. \"auf + Akk\" oder \"Dat\"): {} ... ({})"
+        }
+        Msg::TypeDeclension => "Dekliniere im {} {}",
+        Msg::AfterPreposition => "nach \"{}\"",
+
+        Msg::LanguageRussian => "Russisch",
+        Msg::LanguageEnglish => "Englisch",
+        Msg::MistakeTypo => "Tippfehler",
+        Msg::MistakeUmlaut => "fehlender Umlaut",
+        Msg::MistakeCapitalization => "Groß- und Kleinschreibung",
+        Msg::MistakeArticle => "falscher Artikel",
+        Msg::DefiniteArticle => "mit bestimmtem Artikel",
+        Msg::IndefiniteArticle => "mit unbestimmtem Artikel",
+        Msg::NoArticle => "ohne Artikel",
+        Msg::PosNoun => "Nomen",
+        Msg::PosVerb => "Verb",
+        Msg::PosAdjective => "Adj.",
+        Msg::PosAdverb => "Adv.",
+        Msg::PosPreposition => "Präposition",
+        Msg::PluralArticle => "die (Plural)",
+        Msg::TwoWayPreposition => "Wechselpräposition (Akkusativ/Dativ)",
+
+        Msg::WordsSkipped => "{} Wörter ohne Übersetzung ({}) werden übersprungen",
+        Msg::WordsDue => "{} Wörter sind zur Wiederholung fällig",
+        Msg::NoWordsForExercise => "Keine Wörter für die Übung {}, übersprungen",
+        Msg::QuitHint => "Gib \"exit\" ein oder drücke Strg-C, um das Spiel zu beenden",
+        Msg::SessionSeed => "Startwert der Sitzung ist {0}, mit --seed {0} wird sie wiederholt",
+        Msg::TopWords => "Diese Wörter solltest du lernen: {}",
+        Msg::AllCorrect => "Glückwunsch, alle Antworten sind richtig!",
+        Msg::WordsToRepeat => "{} Wörter sind zu wiederholen",
+        Msg::AllRepeated => "Alle Wörter wiederholt!",
+        Msg::StatsWords => "Wörter: {}, neu: {}, fällig: {}",
+        Msg::StatsAnswers => "Antworten: {} richtig, {} fast richtig, {} falsch",
+        Msg::StatsSkill => "  {} {} richtig {} fast {} falsch",
+        Msg::ReadlineError => "Eingabefehler",
+
+        Msg::MigratedFormat => "Ergebnisse vom Format {} auf {} migriert",
+        Msg::LoadedResults => "Bisherige Ergebnisse geladen, {} Einträge",
+        Msg::CannotLoadResults => "Bisherige Ergebnisse können nicht geladen werden: {}",
+        Msg::OldFileSaved => "Alte Datei gesichert als {}",
+        Msg::CannotBackUp => "Alte Datei kann nicht gesichert werden: {}",
+        Msg::FreshResults => "Beginne mit leeren Ergebnissen",
+        Msg::CannotOpenJournal => "Sitzungsjournal kann nicht geöffnet werden: {}",
+        Msg::RecoveredAnswers => "{} Antworten aus der unterbrochenen Sitzung wiederhergestellt",
+        Msg::CannotSaveResults => "Ergebnisse können nicht gespeichert werden: {}",
+        Msg::CannotClearJournal => "Sitzungsjournal kann nicht geleert werden: {}",
+        Msg::CannotWriteJournal => "Sitzungsjournal kann nicht geschrieben werden: {}",
+        Msg::MigratedWordKeys => "Ergebnisse von {} Wörtern auf Wortkennungen migriert",
+
+        Msg::CannotOpenWorkbook => "{} kann nicht geöffnet werden: {}",
+        Msg::CannotReadSheet => "Blatt {} kann nicht gelesen werden: {}",
+        Msg::NoSheet => "Kein Blatt {} in {}",
+        Msg::MissingColumns => "Pflichtspalten fehlen: {}",
+        Msg::UnknownAliasColumn => "Unbekannte Spalte {} in Aliaszeile {}",
+        Msg::ProblemsFound => "{} Probleme in {} gefunden, Details zeigt der Befehl \"lint\"",
+        Msg::LintProblems => "{} Probleme in {} gefunden",
+        Msg::LintClean => "Keine Probleme in {} gefunden",
+        Msg::IssueRow => "Zeile {}: {}",
+        Msg::IssueRowWord => "Zeile {} ({}): {}",
+        Msg::NoWordsFor => "Keine Wörter für {}",
+        Msg::PoolGame => "das Spiel",
+        Msg::PoolSelectedExercises => "die gewählten Übungen",
+        Msg::ProgressIo => "kein Zugriff auf die Fortschrittsdatei: {}",
+        Msg::ProgressCorrupt => "Fortschrittsdatei ist beschädigt: {}",
+        Msg::ProgressNewer => {
+            "Version {} der Fortschrittsdatei ist neuer als die unterstützte Version {}"
+        }
+
+        Msg::Usage => {
+            "Aufruf: das_woerterbuch [BEFEHL] [OPTIONEN]
+
+Befehle:
+  train    Übungen spielen (Standard)
+  stats    Lernstatistik anzeigen
+  list     Wörter des Wörterbuchs auflisten
+  lint     Wörterbuch auf ungültige Zeilen prüfen
+  help     Diese Hilfe anzeigen
+
+Optionen:
+  --dict <PFAD>         Wörterbuch [Standard: {0}]
+  --progress <PFAD>     Fortschrittsdatei [Standard: {1}]
+  --mode <MODUS>        Übungsmodus, wiederholbar oder durch Kommas getrennt:
+{2}
+  --count <N>           Anzahl der Übungen jeder Art pro Runde [Standard: {3}]
+  --strategy <NAME>     Wortauswahl: spaced-repetition oder score
+  --save-every <N>      Fortschritt alle N Antworten speichern
+  --seed <N>            Startwert der Sitzung, wiederholt dieselben Fragen
+  --language <CODE>     Sprachcode der Übersetzungsspalte, z. B. en [Standard: ru]
+  --ui <CODE>           Sprache der Oberfläche: en, ru oder de [Standard: en]"
+        }
+        Msg::UnexpectedArgument => "Unerwartetes Argument {}",
+        Msg::UnknownCommand => "Unbekannter Befehl {}",
+        Msg::OptionRequiresValue => "Option {} braucht einen Wert",
+        Msg::UnknownMode => "Unbekannter Modus {}",
+        Msg::ExpectsPositiveNumber => "{} erwartet eine positive Zahl, erhalten {}",
+        Msg::ExpectsNumber => "{} erwartet eine Zahl, erhalten {}",
+        Msg::UnknownLanguage => "Ungültiger Sprachcode {}",
+        Msg::UnknownUiLanguage => "Unbekannte Sprache der Oberfläche {}",
+        Msg::UnknownStrategy => "Unbekannte Strategie {}",
+        Msg::UnknownOption => "Unbekannte Option {}",
+        Msg::EmptyWord => "leeres Wort",
+        Msg::EmptyPartOfSpeech => "leere Wortart",
+        Msg::UnknownPartOfSpeech => "unbekannte Wortart {}",
+        Msg::EmptyTranslation => "leere Übersetzung",
+        Msg::EmptyGroup => "unbekannte Gruppe: die Gruppe ist leer",
+        Msg::UnrecognizedPlural => "unbekannter Plural {}",
+        Msg::PerfectWithoutAuxiliary => "Partizip II ohne Hilfsverb",
+        Msg::DuplicateWord => {
+            "doppeltes Wort {0}, das Wort aus Zeile {1} wird verwendet, eine ID behält beide"
+        }
+        Msg::UnknownArticle => "unbekannter Artikel {}",
+        Msg::UnknownPerfectVerb => "unbekanntes Hilfsverb {}",
+        Msg::PresentFormMismatch => "Präsensform {0} passt nicht zum Verb {1}",
+        Msg::VerbFormMismatch => "Verbform {0} passt nicht zum Verb {1}",
+        Msg::ComparativeNeedsSuperlative => "unregelmäßiger Komparativ {} braucht einen Superlativ",
+        Msg::UnknownPrepositionCase => "unbekannter Kasus der Präposition {}",
+        Msg::UnknownGovernmentCase => "unbekannter Kasus {} in der Rektion",
+        Msg::InvalidGovernment => "ungültige Rektion {}",
+        Msg::NestedParentheses => "verschachtelte Klammern in {}",
+        Msg::UnbalancedParentheses => "unpaarige Klammern in {}",
+        Msg::EmptyAlternative => "leere Alternative in {}",
+        Msg::TooManyOptionalParts => "mehr als {0} optionale Teile in {1}",
+        Msg::EmptyOptionalPart => "leerer optionaler Teil in {}",
+    }
+}
+
+/// Text of the message in the selected locale
+pub fn text(msg: Msg) -> &'static str {
+    match locale() {
+        Locale::En => english(msg),
+        Locale::Ru => russian(msg),
+        Locale::De => german(msg),
+    }
+}
+
+/// Text of the message with the placeholders replaced by the arguments
+pub fn fill(msg: Msg, args: &[&dyn Display]) -> String {
+    let mut rest = text(msg);
+    let mut filled = String::new();
+    let mut next = 0;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        filled.push_str(&rest[..start]);
+        let index = match &rest[start + 1..start + len] {
+            "" => {
+                next += 1;
+                next - 1
+            }
+            i => i.parse().unwrap_or(usize::MAX),
+        };
+        if let Some(arg) = args.get(index) {
+            filled.push_str(&arg.to_string());
+        }
+        rest = &rest[start + len + 1..];
+    }
+    filled.push_str(rest);
+    filled
+}
@@ -0,0 +1,321 @@
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Verdict;
+use crate::exercise::{ExerciseResults, Skill};
+use crate::messages::{fill, Msg};
+
+const MAGIC: [u8; 4] = *b"DWPF";
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    magic: [u8; 4],
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ProgressData {
+    /// Sequence number of the last journal entry included in the results
+    pub last_seq: u64,
+    pub results: Vec<ExerciseResults>,
+}
+
+#[derive(Serialize)]
+struct ProgressDataRef<'a> {
+    last_seq: u64,
+    results: &'a [ExerciseResults],
+}
+
+/// Single answer appended to the session journal before it is applied
+#[derive(Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub time: u64,
+    pub id: String,
+    pub skills: Vec<Skill>,
+    pub verdict: Verdict,
+}
+
+/// Failure to load or save the progress file
+#[derive(Debug)]
+pub enum ProgressError {
+    /// File cannot be read or written
+    Io(std::io::Error),
+    /// File is not a progress file or is damaged
+    Corrupt(String),
+    /// File was written by a newer version of the game
+    UnsupportedVersion(u32),
+}
+
+impl Display for ProgressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", fill(Msg::ProgressIo, &[e])),
+            Self::Corrupt(e) => write!(f, "{}", fill(Msg::ProgressCorrupt, &[e])),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "{}", fill(Msg::ProgressNewer, &[v, &FORMAT_VERSION]))
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for ProgressError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<bincode::Error> for ProgressError {
+    fn from(e: bincode::Error) -> Self {
+        Self::Corrupt(e.to_string())
+    }
+}
+
+/// Layout of the progress files written before the header was added
+mod legacy {
+    use serde::Deserialize;
+
+    use crate::exercise::ExerciseResults;
+
+    /// Unversioned file: bare `Vec` of word results without a header
+    #[derive(Deserialize)]
+    pub struct ResultsV0 {
+        word: String,
+        correct: usize,
+        wrong: usize,
+    }
+
+    impl From<ResultsV0> for ExerciseResults {
+        fn from(r: ResultsV0) -> Self {
+            ExerciseResults::with_counts(&r.word, r.correct, r.wrong)
+        }
+    }
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+}
+
+pub struct LoadedProgress {
+    pub data: ProgressData,
+    pub migrated_from: Option<u32>,
+}
+
+pub fn load(path: &Path) -> Result<LoadedProgress, ProgressError> {
+    let bytes = std::fs::read(path)?;
+    if !bytes.starts_with(&MAGIC) {
+        return Ok(LoadedProgress {
+            data: ProgressData {
+                last_seq: 0,
+                results: load_unversioned(&bytes)?,
+            },
+            migrated_from: Some(0),
+        });
+    }
+
+    let header_len = bincode_options().serialized_size(&Header {
+        magic: MAGIC,
+        version: FORMAT_VERSION,
+    })? as usize;
+    if bytes.len() < header_len {
+        return Err(ProgressError::Corrupt("truncated header".to_owned()));
+    }
+    let (header, payload) = bytes.split_at(header_len);
+    let header: Header = bincode_options().deserialize(header)?;
+    let data = match header.version {
+        FORMAT_VERSION => bincode_options().deserialize(payload)?,
+        v => return Err(ProgressError::UnsupportedVersion(v)),
+    };
+    Ok(LoadedProgress {
+        data,
+        migrated_from: None,
+    })
+}
+
+/// Files without the header hold the word results of the first versions of the game
+fn load_unversioned(bytes: &[u8]) -> Result<Vec<ExerciseResults>, ProgressError> {
+    let old: Vec<legacy::ResultsV0> = bincode_options().deserialize(bytes)?;
+    Ok(old.into_iter().map(ExerciseResults::from).collect())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Write the results to a temporary file and move it over the old one,
+/// so that the progress file is never left half-written
+pub fn save(path: &Path, last_seq: u64, results: &[ExerciseResults]) -> Result<(), ProgressError> {
+    let tmp_path = with_suffix(path, ".tmp");
+    let f = File::create(&tmp_path)?;
+    let mut writer = std::io::BufWriter::new(f);
+    let header = Header {
+        magic: MAGIC,
+        version: FORMAT_VERSION,
+    };
+    bincode_options().serialize_into(&mut writer, &header)?;
+    bincode_options().serialize_into(&mut writer, &ProgressDataRef { last_seq, results })?;
+    let f = writer.into_inner().map_err(|e| e.into_error())?;
+    f.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+pub fn journal_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".journal")
+}
+
+/// Append-only log of the answers given since the last save
+pub struct Journal {
+    file: File,
+}
+
+impl Journal {
+    /// Open the journal to append the answers, the file is created when missing
+    pub fn open_for_append(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    pub fn append(&mut self, entry: &JournalEntry) -> Result<(), ProgressError> {
+        let bytes = bincode_options().serialize(entry)?;
+        self.file.write_all(&bytes)?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        self.file.set_len(0)
+    }
+
+    /// Read all complete entries without creating or changing the file,
+    /// a missing journal has no entries and a truncated last entry is ignored
+    pub fn open_for_replay(path: &Path) -> std::io::Result<Vec<JournalEntry>> {
+        let mut entries = vec![];
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(entries),
+            Err(e) => return Err(e),
+        };
+        let mut reader = std::io::BufReader::new(file);
+        let options = bincode::DefaultOptions::new().with_fixint_encoding();
+        while let Ok(entry) = options.deserialize_from(&mut reader) {
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// Move unreadable progress file aside so that the game can start afresh
+pub fn backup(path: &Path) -> std::io::Result<PathBuf> {
+    let backup = with_suffix(path, &format!(".{}.bak", crate::scheduler::now()));
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("dw-progress-{}-{}", std::process::id(), name))
+    }
+
+    fn versioned(version: u32, payload: &impl Serialize) -> Vec<u8> {
+        let header = Header {
+            magic: MAGIC,
+            version,
+        };
+        let mut bytes = bincode_options().serialize(&header).unwrap();
+        bytes.extend(bincode_options().serialize(payload).unwrap());
+        bytes
+    }
+
+    fn load_bytes(name: &str, bytes: &[u8]) -> Result<LoadedProgress, ProgressError> {
+        let path = temp_path(name);
+        std::fs::write(&path, bytes).unwrap();
+        let loaded = load(&path);
+        std::fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    fn article_skill() -> Skill {
+        Skill::Exercise("guess-noun-article".to_owned())
+    }
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let v0 = vec![("haus".to_owned(), 3usize, 1usize)];
+        let loaded = load_bytes("v0", &bincode_options().serialize(&v0).unwrap()).unwrap();
+        assert_eq!(loaded.migrated_from, Some(0));
+        let r = &loaded.data.results[0];
+        assert_eq!((r.id(), r.correct(), r.wrong()), ("haus", 3, 1));
+        assert!(r.schedule().is_new());
+    }
+
+    #[test]
+    fn saved_file_is_loaded_back() {
+        let path = temp_path("current");
+        let mut results = ExerciseResults::new("n:das:haus");
+        results.add(Verdict::Correct, &[article_skill()], 100);
+        save(&path, 3, std::slice::from_ref(&results)).unwrap();
+        let loaded = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.migrated_from, None);
+        assert_eq!(loaded.data.last_seq, 3);
+        assert_eq!(loaded.data.results[0].correct(), 1);
+        assert!(loaded.data.results[0].skill(&article_skill()).is_some());
+    }
+
+    #[test]
+    fn reports_errors_by_kind() {
+        let newer = load_bytes("newer", &versioned(FORMAT_VERSION + 1, &0u64));
+        assert!(matches!(newer, Err(ProgressError::UnsupportedVersion(_))));
+        let corrupt = load_bytes("corrupt", b"DWPF\x01\x00\x00\x00garbage");
+        assert!(matches!(corrupt, Err(ProgressError::Corrupt(_))));
+        assert!(matches!(
+            load(&std::env::temp_dir()),
+            Err(ProgressError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn backup_moves_the_file_aside() {
+        let path = temp_path("backup");
+        std::fs::write(&path, b"old").unwrap();
+        let backup = backup(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(std::fs::read(&backup).unwrap(), b"old");
+        std::fs::remove_file(backup).unwrap();
+    }
+
+    #[test]
+    fn replay_does_not_create_the_journal() {
+        let path = temp_path("missing-journal");
+        let entries = Journal::open_for_replay(&path).unwrap();
+        assert!(entries.is_empty());
+        assert!(!path.exists());
+
+        let mut journal = Journal::open_for_append(&path).unwrap();
+        let entry = JournalEntry {
+            seq: 1,
+            time: 100,
+            id: "n:das:haus".to_owned(),
+            skills: vec![],
+            verdict: Verdict::Correct,
+        };
+        journal.append(&entry).unwrap();
+        let entries = Journal::open_for_replay(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+}
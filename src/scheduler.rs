@@ -0,0 +1,250 @@
+//! Spaced repetition schedule of the words
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use strum_macros::{EnumIter, EnumString};
+
+use crate::messages::{text, Msg};
+
+const DEFAULT_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+const DAY_SECS: u64 = 24 * 60 * 60;
+const RELEARN_DELAY_SECS: u64 = 10 * 60;
+const MAX_REVIEW_HISTORY: usize = 20;
+const DUE_POOL_SIZE: usize = 5;
+/// New words introduced in a session by the spaced repetition
+pub const NEW_WORDS_PER_SESSION: usize = 10;
+
+/// Current time in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// How the next word to ask is selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SelectionStrategy {
+    /// Due words first, then new words, by the SM-2 schedule
+    SpacedRepetition,
+    /// Words with the lowest score first
+    Score,
+}
+
+impl SelectionStrategy {
+    /// Name of the strategy in the menu
+    pub fn description(self) -> &'static str {
+        text(match self {
+            SelectionStrategy::SpacedRepetition => Msg::StrategySpacedRepetition,
+            SelectionStrategy::Score => Msg::StrategyScore,
+        })
+    }
+}
+
+/// SM-2 review state of a single word
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Schedule {
+    /// Multiplier of the interval after a correct review
+    pub ease: f32,
+    /// Days until the next review
+    pub interval_days: f32,
+    /// Correct reviews in a row
+    pub repetitions: u32,
+    /// Time of the next review in seconds since the Unix epoch
+    pub due: u64,
+    /// Times of the last reviews
+    pub reviews: Vec<u64>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            ease: DEFAULT_EASE,
+            interval_days: 0.0,
+            repetitions: 0,
+            due: 0,
+            reviews: vec![],
+        }
+    }
+}
+
+impl Schedule {
+    /// Word which was never reviewed
+    pub fn is_new(&self) -> bool {
+        self.reviews.is_empty()
+    }
+
+    /// Word which was reviewed and should be reviewed again by now
+    pub fn is_due(&self, now: u64) -> bool {
+        !self.is_new() && self.due <= now
+    }
+
+    /// Update the schedule with answer quality from 0 (blackout) to 5 (perfect).
+    /// A correct answer given before the word is due keeps the interval
+    pub fn review(&mut self, quality: u8, now: u64) {
+        let quality = quality.min(5);
+        if quality >= 3 && now < self.due {
+            self.push_review(now);
+            return;
+        }
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1.0,
+                1 => 6.0,
+                _ => self.interval_days * self.ease,
+            };
+            self.repetitions += 1;
+            self.due = now + (self.interval_days * DAY_SECS as f32) as u64;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 0.0;
+            self.due = now + RELEARN_DELAY_SECS;
+        }
+        let q = (5 - quality) as f32;
+        self.ease = (self.ease + 0.1 - q * (0.08 + q * 0.02)).max(MIN_EASE);
+        self.push_review(now);
+    }
+
+    fn push_review(&mut self, now: u64) {
+        self.reviews.push(now);
+        if self.reviews.len() > MAX_REVIEW_HISTORY {
+            self.reviews.remove(0);
+        }
+    }
+}
+
+/// Chooses the next word to review from the candidates
+pub struct Scheduler {
+    new_words_limit: usize,
+    new_words_introduced: usize,
+}
+
+impl Scheduler {
+    /// Scheduler introducing at most the number of new words
+    pub fn new(new_words_limit: usize) -> Self {
+        Self {
+            new_words_limit,
+            new_words_introduced: 0,
+        }
+    }
+
+    /// Select one of the candidates: overdue words first, then new words
+    /// while the session limit allows, then the words reviewed before.
+    /// New words beyond the limit are taken only when no reviewed word is left
+    pub fn select(
+        &mut self,
+        rng: &mut impl Rng,
+        candidates: &[(usize, &Schedule)],
+        now: u64,
+    ) -> Option<usize> {
+        let mut due: Vec<_> = candidates.iter().filter(|(_, s)| s.is_due(now)).collect();
+        if !due.is_empty() {
+            due.sort_by_key(|(_, s)| s.due);
+            due.truncate(DUE_POOL_SIZE);
+            return due.choose(rng).map(|(i, _)| *i);
+        }
+
+        let (new, reviewed): (Vec<_>, Vec<_>) = candidates.iter().partition(|(_, s)| s.is_new());
+        if !new.is_empty() && self.new_words_introduced < self.new_words_limit {
+            self.new_words_introduced += 1;
+            return new.choose(rng).map(|(i, _)| *i);
+        }
+
+        reviewed
+            .choose(rng)
+            .or_else(|| new.choose(rng))
+            .map(|(i, _)| *i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_on_time_grows_interval() {
+        let mut schedule = Schedule::default();
+        schedule.review(4, 0);
+        assert_eq!(schedule.repetitions, 1);
+        assert_eq!(schedule.interval_days, 1.0);
+        assert_eq!(schedule.due, DAY_SECS);
+
+        schedule.review(4, DAY_SECS);
+        assert_eq!(schedule.repetitions, 2);
+        assert_eq!(schedule.interval_days, 6.0);
+        assert_eq!(schedule.due, 7 * DAY_SECS);
+    }
+
+    #[test]
+    fn early_correct_review_keeps_interval() {
+        let mut schedule = Schedule::default();
+        schedule.review(4, 0);
+        let ease = schedule.ease;
+        schedule.review(5, 100);
+        assert_eq!(schedule.repetitions, 1);
+        assert_eq!(schedule.interval_days, 1.0);
+        assert_eq!(schedule.due, DAY_SECS);
+        assert_eq!(schedule.ease, ease);
+        assert_eq!(schedule.reviews, vec![0, 100]);
+    }
+
+    #[test]
+    fn early_wrong_review_resets_interval() {
+        let mut schedule = Schedule::default();
+        schedule.review(4, 0);
+        schedule.review(1, 100);
+        assert_eq!(schedule.repetitions, 0);
+        assert_eq!(schedule.interval_days, 0.0);
+        assert_eq!(schedule.due, 100 + RELEARN_DELAY_SECS);
+    }
+
+    #[test]
+    fn select_prefers_due_words() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut learned = Schedule::default();
+        learned.review(4, 0);
+        let new = Schedule::default();
+        let candidates = [(0, &new), (1, &learned)];
+        let mut scheduler = Scheduler::new(NEW_WORDS_PER_SESSION);
+        assert_eq!(scheduler.select(&mut rng, &candidates, DAY_SECS), Some(1));
+        assert_eq!(scheduler.select(&mut rng, &candidates, 0), Some(0));
+        assert_eq!(scheduler.select(&mut rng, &[], 0), None);
+    }
+
+    #[test]
+    fn new_words_over_the_limit_only_when_nothing_else_is_left() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut learned = Schedule::default();
+        learned.review(4, 0);
+        let new = Schedule::default();
+        let mut scheduler = Scheduler::new(0);
+        for _ in 0..10 {
+            assert_eq!(
+                scheduler.select(&mut rng, &[(0, &new), (1, &learned)], 0),
+                Some(1)
+            );
+        }
+        assert_eq!(scheduler.select(&mut rng, &[(0, &new)], 0), Some(0));
+    }
+
+    #[test]
+    fn session_does_not_push_words_far_ahead() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut schedules = vec![Schedule::default(); 100];
+        let mut scheduler = Scheduler::new(NEW_WORDS_PER_SESSION);
+        for pick in 0..60 {
+            let now = pick * 60;
+            let idx = {
+                let candidates: Vec<_> = schedules.iter().enumerate().collect();
+                scheduler.select(&mut rng, &candidates, now).unwrap()
+            };
+            schedules[idx].review(4, now);
+        }
+        let asked = schedules.iter().filter(|s| !s.is_new()).count();
+        assert!(asked <= NEW_WORDS_PER_SESSION);
+        assert!(schedules.iter().all(|s| s.interval_days <= 1.0));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::Display;
+
+use crate::answer::AnswerSpec;
+use crate::columns::{Column, WordRow};
+use crate::messages::{fill, text, Msg};
+use crate::words::{
+    get_article, parse_comparison, parse_plural, Government, NounArticle, PerfectVerb,
+    PrepositionCase, VerbParts,
+};
+
+/// Parts of speech which may be present in the workbook but are not used in the game
+const IGNORED_POS: [&str; 1] = ["phrase"];
+
+/// Problem found in a row of the Words sheet
+#[derive(Debug)]
+pub struct RowIssue {
+    /// Row number as shown in the spreadsheet
+    pub row: usize,
+    /// Word of the row, empty if the problem is not with a single word
+    pub word: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Display for RowIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = if self.word.is_empty() {
+            fill(Msg::IssueRow, &[&self.row, &self.message])
+        } else {
+            fill(Msg::IssueRowWord, &[&self.row, &self.word, &self.message])
+        };
+        write!(f, "{}", line)
+    }
+}
+
+pub enum RowKind {
+    Word,
+    Ignored,
+}
+
+fn is_empty(row: &WordRow, column: Column) -> bool {
+    row.get(column).trim().is_empty()
+}
+
+/// Check a row of the Words sheet and collect all problems found in it
+pub fn validate_row(row: &WordRow) -> Result<RowKind, Vec<String>> {
+    let mut problems = vec![];
+    let pos = row.get(Column::PartOfSpeech).trim();
+
+    if is_empty(row, Column::Word) {
+        if pos.is_empty() && !row.has_translation() {
+            // empty line between the words
+            return Ok(RowKind::Ignored);
+        }
+        problems.push(text(Msg::EmptyWord).to_owned());
+    }
+    match pos {
+        "n" | "v" | "adj" | "adv" | "prep" => {}
+        "" => problems.push(text(Msg::EmptyPartOfSpeech).to_owned()),
+        p if IGNORED_POS.contains(&p) => return Ok(RowKind::Ignored),
+        p => problems.push(fill(Msg::UnknownPartOfSpeech, &[&format!("{:?}", p)])),
+    }
+    if !row.has_translation() {
+        problems.push(text(Msg::EmptyTranslation).to_owned());
+    }
+    if is_empty(row, Column::Group) {
+        problems.push(text(Msg::EmptyGroup).to_owned());
+    }
+
+    let answer_cells = [
+        Column::Word,
+        Column::Perfect,
+        Column::PresentThird,
+        Column::Praeteritum,
+    ]
+    .map(|column| row.get(column));
+    let mut translations: Vec<_> = row.translations.values().map(|s| s.as_str()).collect();
+    translations.sort();
+    for cell in answer_cells.into_iter().chain(translations) {
+        if let Err(e) = AnswerSpec::parse_strict(cell) {
+            problems.push(e);
+        }
+    }
+    if let Err(e) = Government::parse(row.get(Column::Government)) {
+        problems.push(e);
+    }
+    match pos {
+        "n" => match get_article(row.get(Column::Article).trim()) {
+            Err(e) => problems.push(e),
+            Ok(NounArticle::Plural) => {}
+            Ok(_) => {
+                let plural = row.get(Column::Plural).trim();
+                if !plural.is_empty()
+                    && !is_empty(row, Column::Word)
+                    && parse_plural(row.get(Column::Word), plural).is_none()
+                {
+                    problems.push(fill(Msg::UnrecognizedPlural, &[&format!("{:?}", plural)]));
+                }
+            }
+        },
+        "v" => {
+            match PerfectVerb::from(row.get(Column::PerfectVerb)) {
+                Err(e) => problems.push(e),
+                Ok(None) if !is_empty(row, Column::Perfect) => {
+                    problems.push(text(Msg::PerfectWithoutAuxiliary).to_owned())
+                }
+                Ok(_) => {}
+            }
+            match VerbParts::parse(row.get(Column::Word), row.get(Column::PresentThird)) {
+                Err(e) => problems.push(e),
+                Ok(parts) => {
+                    if let Err(e) = parts.finite_form(row.get(Column::Praeteritum)) {
+                        problems.push(e);
+                    }
+                }
+            }
+        }
+        "adj" => {
+            if let Err(e) = parse_comparison(
+                row.get(Column::Word),
+                row.get(Column::Comparative),
+                row.get(Column::Superlative),
+            ) {
+                problems.push(e);
+            }
+        }
+        "prep" => {
+            if let Err(e) = PrepositionCase::from(row.get(Column::Case)) {
+                problems.push(e);
+            }
+        }
+        _ => {}
+    }
+
+    if problems.is_empty() {
+        Ok(RowKind::Word)
+    } else {
+        Err(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::word_row;
+
+    const NOUN: [&str; 6] = ["Word", "PoS", "Translation", "Group", "Article", "Plural"];
+
+    #[test]
+    fn bad_plural_is_reported() {
+        let row = word_row(&NOUN, &["Baum", "n", "дерево", "1", "der", "¨e"]);
+        assert!(matches!(validate_row(&row), Ok(RowKind::Word)));
+
+        let row = word_row(&NOUN, &["Hund", "n", "собака", "1", "der", "e"]);
+        let problems = validate_row(&row).err().unwrap();
+        assert_eq!(problems, vec!["unrecognized plural \"e\"".to_owned()]);
+    }
+
+    #[test]
+    fn bad_answer_cells_are_reported() {
+        let row = word_row(
+            &NOUN,
+            &["Bank, Sitzbank", "n", "скамья (лавка", "1", "die", ""],
+        );
+        let problems = validate_row(&row).err().unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("unbalanced parentheses"));
+    }
+}
@@ -1,23 +1,75 @@
-use std::{collections::HashMap, fmt::Display};
+//! Words of the dictionary by parts of speech
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use crate::answer::AnswerSpec;
+pub use crate::columns::{Column, WordRow};
+use crate::conjugation::{conjugate, Person, Tense};
+use crate::declension::Case;
+use crate::language::Language;
+use crate::messages::{fill, text, Msg};
 
 use strum_macros::EnumIter;
 
+/// Part of speech of the word, it defines the exercises the word is used in
 #[derive(Debug, Clone, Copy, EnumIter, PartialEq)]
 pub enum PartOfSpeech {
+    /// Noun with the article
     Noun,
+    /// Verb with its forms
     Verb,
+    /// Adjective with the comparison forms
     Adjective,
+    /// Adverb
     Adverb,
+    /// Preposition with the governed case
     Preposition,
 }
 
-fn umlaut_normalize(word: &str) -> String {
+impl PartOfSpeech {
+    /// Code used in the PoS column of the dictionary
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Noun => "n",
+            Self::Verb => "v",
+            Self::Adjective => "adj",
+            Self::Adverb => "adv",
+            Self::Preposition => "prep",
+        }
+    }
+
+    /// Name in the interface language
+    pub fn name(&self) -> &'static str {
+        text(match self {
+            Self::Noun => Msg::PosNoun,
+            Self::Verb => Msg::PosVerb,
+            Self::Adjective => Msg::PosAdjective,
+            Self::Adverb => Msg::PosAdverb,
+            Self::Preposition => Msg::PosPreposition,
+        })
+    }
+}
+
+/// Identity of a word which tells apart homonyms, e.g. `n:der:see` and `n:die:see`
+pub fn make_word_id(pos: PartOfSpeech, article: Option<NounArticle>, word: &str) -> String {
+    let word = word.trim().to_lowercase();
+    match article {
+        Some(NounArticle::Plural) => format!("{}:pl:{}", pos.code(), word),
+        Some(article) => format!("{}:{}:{}", pos.code(), article, word),
+        None => format!("{}:{}", pos.code(), word),
+    }
+}
+
+/// Spell the umlauts and ß with two letters: Übung - Uebung
+pub fn umlaut_normalize(word: &str) -> String {
     word.replace('ü', "ue")
         .replace('ä', "ae")
         .replace('ö', "oe")
         .replace('ß', "ss")
 }
 
+/// Compare ignoring the case, the umlauts may be spelled with two letters
 pub fn check_spelling_simple(answer: &str, expected: &str) -> bool {
     let low_ans = answer.to_lowercase();
     let spelling = expected.to_lowercase();
@@ -28,98 +80,169 @@ pub fn check_spelling_simple(answer: &str, expected: &str) -> bool {
     }
 }
 
-pub fn check_spelling_perfect(answer: &str, expected: &dyn Word) -> bool {
-    let mut frags = answer.split_whitespace();
-    let first = match frags.next() {
-        None => {
-            return false;
-        }
-        Some(s) => s,
+/// Accepted perfect forms with the auxiliary verb: `hat gelesen`, both auxiliary verbs
+/// for `hat/ist`
+pub fn perfect_answers(word: &dyn Word) -> Option<AnswerSpec> {
+    let participle = word.get_verb_perfect()?;
+    let auxiliaries: &[&str] = match word.get_verb_perfect_verb()? {
+        PerfectVerb::Haben => &["hat"],
+        PerfectVerb::Sein => &["ist"],
+        PerfectVerb::Both => &["hat", "ist"],
     };
-    let second = match frags.next() {
-        None => {
-            return false;
-        }
-        Some(s) => s,
+    let sich = match word.get_verb_parts() {
+        Some(parts) if parts.reflexive => "sich ",
+        _ => "",
     };
-    if first != "hat" && first != "ist" {
-        return false;
-    }
-    if !expected
-        .get_verb_perfect_verb()
-        .unwrap()
-        .to_string()
-        .contains(first)
-    {
-        return false;
-    }
-    check_spelling_simple(second, expected.get_verb_perfect().unwrap())
+    Some(AnswerSpec::from_alternatives(
+        auxiliaries
+            .iter()
+            .flat_map(|aux| {
+                participle
+                    .alternatives()
+                    .iter()
+                    .map(move |p| format!("{} {}{}", aux, sich, p))
+            })
+            .collect(),
+    ))
 }
 
+/// Dictionary entry of any part of speech, the forms missing for it are `None`
 pub trait Word {
+    /// Part of speech as shown in the questions
     fn pos_str(&self) -> &'static str {
-        unimplemented!()
+        self.get_pos().name()
     }
 
-    fn translation(&self) -> &str;
+    /// Translation to the language, empty if the word is not translated to it
+    fn translation(&self, lang: Language) -> &str;
+
+    /// Translations accepted in the typed exercises
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec>;
 
+    /// Word as shown in the exercises, with the article for the nouns
     fn spelling(&self) -> String {
         self.get_word().to_owned()
     }
 
-    fn check_spelling(&self, answer: &str) -> bool {
-        check_spelling_simple(answer, &self.spelling())
-    }
+    /// Spellings accepted in the typed exercises
+    fn get_answers(&self) -> &AnswerSpec;
 
+    /// Word as written in the dictionary
     fn get_word(&self) -> &str;
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self
+    /// Stable identity of the word used as a key in the database and the results
+    fn get_id(&self) -> &str;
+
+    /// Change the identity, used to tell apart the homonyms of the dictionary
+    fn set_id(&mut self, id: String);
+
+    /// Parse the word from the cells of the row, the used cells are taken out of it
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String>
     where
         Self: Sized;
 
+    /// Hint shown in the translation exercise
     fn get_help(&self) -> &str;
 
+    /// Values of the user defined columns which are not used by the game
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        vec![]
+    }
+
+    /// Index of the group in the database
     fn get_group_id(&self) -> usize;
 
+    /// Part of speech of the word
     fn get_pos(&self) -> PartOfSpeech;
 
+    /// Article of the noun
     fn get_article(&self) -> Option<NounArticle> {
         None
     }
 
-    fn get_verb_praeteritum(&self) -> Option<&str> {
+    /// Präteritum of the verb: las
+    fn get_verb_praeteritum(&self) -> Option<&AnswerSpec> {
         None
     }
 
-    fn get_verb_perfect(&self) -> Option<&str> {
+    /// Participle II of the verb: gelesen
+    fn get_verb_perfect(&self) -> Option<&AnswerSpec> {
         None
     }
 
+    /// Auxiliary verb of the perfect
     fn get_verb_perfect_verb(&self) -> Option<&PerfectVerb> {
         None
     }
 
+    /// Perfect with the auxiliary verb: hat gelesen
     fn get_verb_perfect_full(&self) -> Option<String> {
         None
     }
 
-    fn get_verb_present_third(&self) -> Option<&str> {
+    /// Third person singular of the present tense: liest
+    fn get_verb_present_third(&self) -> Option<&AnswerSpec> {
+        None
+    }
+
+    /// Verb conjugated in the person, `None` if the tense cannot be derived
+    fn get_verb_conjugation(&self, _tense: Tense, _person: Person) -> Option<String> {
+        None
+    }
+
+    /// Separable prefix and reflexive pronoun of the verb
+    fn get_verb_parts(&self) -> Option<&VerbParts> {
+        None
+    }
+
+    /// Preposition and case governed by the verb or the adjective
+    fn get_government(&self) -> Option<&Government> {
+        None
+    }
+
+    /// Take the forms missing in the dictionary from the verb without the separable
+    /// prefix: ankommen - kommen
+    fn inherit_verb_forms(&mut self, _base: &dyn Word) {}
+
+    /// Plural of the noun
+    fn get_plural(&self) -> Option<&str> {
+        None
+    }
+
+    /// Case governed by the preposition
+    fn get_preposition_case(&self) -> Option<PrepositionCase> {
+        None
+    }
+
+    /// Comparative of the adjective: kleiner
+    fn get_comparative(&self) -> Option<&str> {
+        None
+    }
+
+    /// Superlative of the adjective: am kleinsten
+    fn get_superlative(&self) -> Option<&str> {
         None
     }
 }
 
+/// Article of the noun, `Plural` for the nouns used only in plural
 #[derive(Debug, EnumIter, PartialEq, Eq, Clone, Copy)]
 pub enum NounArticle {
+    /// Masculine
     Der,
+    /// Neuter
     Das,
+    /// Feminine
     Die,
+    /// Plural only noun: die Leute
     Plural,
 }
 
 impl NounArticle {
+    /// Article as shown in the options of the article exercise
     pub fn answer_bullet_str(&self) -> String {
         match self {
-            Self::Plural => "die (plural)".to_string(),
+            Self::Plural => text(Msg::PluralArticle).to_owned(),
             _ => self.to_string(),
         }
     }
@@ -137,107 +260,271 @@ impl Display for NounArticle {
     }
 }
 
+/// Fields shared by the words of all parts of speech
 #[derive(Debug)]
 pub struct WordCommon {
+    /// Stable identity of the word, see [`Word::get_id`]
+    pub id: String,
+    /// Word as written in the dictionary
     pub word: String,
+    /// Spellings of the word accepted as the answer
+    pub answers: AnswerSpec,
+    /// Index of the group in the database
     pub group_id: usize,
-    pub translation: String,
+    /// Translations by language
+    pub translations: HashMap<Language, String>,
+    /// Accepted answers of the translations by language
+    pub translation_answers: HashMap<Language, AnswerSpec>,
+    /// Hint shown in the translation exercise
     pub help: String,
+    /// Cells of the user defined columns by their header
+    pub extra: HashMap<String, String>,
 }
 
-const WORD_IDX: usize = 0;
-const POS_IDX: usize = 1;
-const TRANSLATION_IDX: usize = 2;
-const GROUP_IDX: usize = 3;
-const ARTICLE_IDX: usize = 4;
-const HELP_IDX: usize = 7;
-const PERFECT_IDX: usize = 5;
-const PRAETERITUM_IDX: usize = 6;
-const PERFECT_VERB_IDX: usize = 8;
-const PRESENT_THIRD_IDX: usize = 9;
-
-pub fn get_part_of_speech(map: &HashMap<usize, String>) -> &str {
-    &map[&POS_IDX]
-}
-
-impl Word for WordCommon {
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            word: map.remove(&WORD_IDX).unwrap(),
-            group_id: db.get_group_id(&map.remove(&GROUP_IDX).unwrap()),
-            translation: map.remove(&TRANSLATION_IDX).unwrap(),
-            help: map.remove(&HELP_IDX).unwrap(),
+/// Part of speech cell of the row
+pub fn get_part_of_speech(row: &WordRow) -> &str {
+    row.get(Column::PartOfSpeech)
+}
+
+impl WordCommon {
+    /// Use the identity derived from the word unless it is set in the dictionary
+    pub fn with_default_id(mut self, pos: PartOfSpeech, article: Option<NounArticle>) -> Self {
+        if self.id.is_empty() {
+            self.id = make_word_id(pos, article, &self.word);
         }
+        self
+    }
+
+    /// Fields shared by all parts of speech, the rest of the row is left for the word
+    pub fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        let word = row.take(Column::Word).unwrap_or_default();
+        let translations = std::mem::take(&mut row.translations);
+        Ok(Self {
+            id: row.take(Column::Id).unwrap_or_default().trim().to_owned(),
+            answers: AnswerSpec::parse(&word),
+            word,
+            group_id: db.get_group_id(&row.take(Column::Group).unwrap_or_default()),
+            translation_answers: translations
+                .iter()
+                .map(|(&lang, cell)| (lang, AnswerSpec::parse(cell)))
+                .collect(),
+            translations,
+            help: row.take(Column::Help).unwrap_or_default(),
+            extra: std::mem::take(&mut row.extra),
+        })
     }
 
-    fn translation(&self) -> &str {
-        &self.translation
+    /// Translation to the language, empty if the word is not translated to it
+    pub fn translation(&self, lang: Language) -> &str {
+        self.translations
+            .get(&lang)
+            .map(|s| s.as_str())
+            .unwrap_or_default()
     }
 
-    fn get_word(&self) -> &str {
+    /// Translations accepted in the typed exercises
+    pub fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.translation_answers.get(&lang)
+    }
+
+    /// Spellings accepted in the typed exercises
+    pub fn get_answers(&self) -> &AnswerSpec {
+        &self.answers
+    }
+
+    /// Word as written in the dictionary
+    pub fn get_word(&self) -> &str {
         &self.word
     }
 
-    fn get_help(&self) -> &str {
+    /// Stable identity of the word
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    /// Hint shown in the translation exercise
+    pub fn get_help(&self) -> &str {
         &self.help
     }
 
-    fn get_group_id(&self) -> usize {
-        self.group_id
+    /// Values of the user defined columns sorted by header
+    pub fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        let mut extra: Vec<_> = self
+            .extra
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        extra.sort();
+        extra
     }
 
-    fn get_pos(&self) -> PartOfSpeech {
-        unimplemented!()
+    /// Index of the group in the database
+    pub fn get_group_id(&self) -> usize {
+        self.group_id
     }
 }
 
-fn get_article(s: &str) -> Result<NounArticle, String> {
-    Ok(match s {
+/// Article by its spelling, `pl` for the plural only nouns
+pub fn get_article(s: &str) -> Result<NounArticle, String> {
+    Ok(match s.trim() {
         "der" => NounArticle::Der,
         "das" => NounArticle::Das,
         "die" => NounArticle::Die,
         "pl" => NounArticle::Plural,
-        _ => return Err(format!("Unknown article {:?}", s)),
+        _ => return Err(fill(Msg::UnknownArticle, &[&format!("{:?}", s)])),
     })
 }
 
+/// Noun with the first letter in upper case, an empty cell stays empty
 pub fn capitalize_noun(noun: &str) -> String {
-    noun.chars().next().unwrap().to_uppercase().to_string()
-        + &noun.chars().skip(1).collect::<String>()
+    let mut chars = noun.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Put umlaut on the last a, o, u or au of the stem: Haus - Häus, Mutter - Mütter
+fn add_umlaut(word: &str) -> String {
+    let mut chars: Vec<char> = word.chars().collect();
+    if let Some(pos) = chars
+        .iter()
+        .rposition(|c| matches!(c, 'a' | 'o' | 'u' | 'A' | 'O' | 'U'))
+    {
+        let is_au = chars[pos] == 'u' && pos > 0 && matches!(chars[pos - 1], 'a' | 'A');
+        let pos = if is_au { pos - 1 } else { pos };
+        chars[pos] = match chars[pos] {
+            'a' => 'ä',
+            'o' => 'ö',
+            'u' => 'ü',
+            'A' => 'Ä',
+            'O' => 'Ö',
+            'U' => 'Ü',
+            c => c,
+        };
+    }
+    chars.into_iter().collect()
 }
 
+/// First letter of the word without umlaut, to compare the singular and the plural
+fn base_letter(word: &str) -> Option<char> {
+    let c = word.chars().next()?.to_lowercase().next()?;
+    Some(match c {
+        'ä' => 'a',
+        'ö' => 'o',
+        'ü' => 'u',
+        c => c,
+    })
+}
+
+/// Build the plural form from the dictionary cell, which contains either the full form
+/// or a shorthand suffix: `-e` (Hund - Hunde), `¨-er` or `¨er` (Haus - Häuser),
+/// `¨e` (Baum - Bäume), `¨` (Mutter - Mütter), `-` (Zimmer - Zimmer).
+/// `None` if the cell is empty or not understood
+pub fn parse_plural(singular: &str, spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    let singular = singular.trim();
+    if spec.is_empty() || singular.is_empty() {
+        return None;
+    }
+    let singular = capitalize_noun(singular);
+    let (umlaut, rest) = match spec.strip_prefix('¨') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, spec),
+    };
+    let suffix = match rest.strip_prefix('-') {
+        Some(suffix) => Some(suffix.trim()),
+        None if umlaut => Some(rest),
+        None => None,
+    };
+    match suffix {
+        Some(suffix) => {
+            if !suffix
+                .chars()
+                .all(|c| c.is_alphabetic() && c.is_lowercase())
+            {
+                return None;
+            }
+            let stem = if umlaut {
+                let stem = add_umlaut(&singular);
+                (stem != singular).then_some(stem)?
+            } else {
+                singular
+            };
+            Some(stem + suffix)
+        }
+        None => {
+            let full = rest.strip_prefix("die ").unwrap_or(rest).trim();
+            let is_word = full.chars().all(|c| c.is_alphabetic() || c == '-');
+            (is_word && base_letter(full) == base_letter(&singular)).then(|| capitalize_noun(full))
+        }
+    }
+}
+
+/// Noun with its article and plural
 #[derive(Debug)]
 pub struct Noun {
+    /// Fields shared by all words
     pub common: WordCommon,
+    /// Article of the noun
     pub article: NounArticle,
+    /// Plural form, `None` if it is not in the dictionary
+    pub plural: Option<String>,
 }
 
 impl Word for Noun {
-    fn pos_str(&self) -> &'static str {
-        "noun"
-    }
-
     fn spelling(&self) -> String {
         self.article.to_string() + " " + &capitalize_noun(&self.common.word)
     }
 
-    fn translation(&self) -> &str {
-        self.common.translation()
+    fn translation(&self, lang: Language) -> &str {
+        self.common.translation(lang)
+    }
+
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.common.get_translation_answers(lang)
+    }
+
+    fn get_answers(&self) -> &AnswerSpec {
+        self.common.get_answers()
     }
 
     fn get_word(&self) -> &str {
         self.common.get_word()
     }
 
+    fn get_id(&self) -> &str {
+        self.common.get_id()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.common.id = id;
+    }
+
     fn get_help(&self) -> &str {
         self.common.get_help()
     }
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            common: WordCommon::new(map, db),
-            article: get_article(&map.remove(&ARTICLE_IDX).unwrap()).unwrap(),
-        }
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        self.common.get_extra_columns()
+    }
+
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        let article = get_article(&row.take(Column::Article).unwrap_or_default())?;
+        let plural_spec = row.take(Column::Plural).unwrap_or_default();
+        let plural = match article {
+            NounArticle::Plural => None,
+            _ => parse_plural(row.get(Column::Word), &plural_spec),
+        };
+        let mut common = WordCommon::new(row, db)?;
+        common.answers = common
+            .answers
+            .map(|alt| format!("{} {}", article, capitalize_noun(alt)));
+        Ok(Self {
+            article,
+            plural,
+            common: common.with_default_id(PartOfSpeech::Noun, Some(article)),
+        })
     }
 
     fn get_group_id(&self) -> usize {
@@ -251,32 +538,45 @@ impl Word for Noun {
     fn get_article(&self) -> Option<NounArticle> {
         Some(self.article)
     }
+
+    fn get_plural(&self) -> Option<&str> {
+        self.plural.as_deref()
+    }
 }
 
-#[derive(Debug)]
+/// Auxiliary verb of the perfect
+#[derive(Debug, Clone, Copy)]
 pub enum PerfectVerb {
+    /// hat gelesen
     Haben,
+    /// ist gegangen
     Sein,
+    /// hat or ist depending on the meaning: hat/ist gefahren
     Both,
 }
 
 impl PerfectVerb {
-    pub fn from(s: &str) -> Option<Self> {
-        Some(match s.trim() {
+    /// Auxiliary verb by the cell: `hat`, `ist`, `hat/ist`, `None` for an empty cell
+    pub fn from(s: &str) -> Result<Option<Self>, String> {
+        Ok(Some(match s.trim() {
             "hat" => PerfectVerb::Haben,
             "ist" => PerfectVerb::Sein,
             "hat/ist" => PerfectVerb::Both,
-            s if s.is_empty() => {
-                return None;
+            "" => {
+                return Ok(None);
             }
             _ => {
-                panic!("Unknown perfect verb {}", s);
+                return Err(fill(Msg::UnknownPerfectVerb, &[&format!("{:?}", s)]));
             }
-        })
+        }))
     }
 
-    pub fn from_option(s: Option<String>) -> Option<Self> {
-        Self::from(&s?)
+    /// Auxiliary verb of the cell if the column is present
+    pub fn from_option(s: Option<String>) -> Result<Option<Self>, String> {
+        match s {
+            Some(s) => Self::from(&s),
+            None => Ok(None),
+        }
     }
 }
 
@@ -291,42 +591,234 @@ impl Display for PerfectVerb {
     }
 }
 
+/// Prefixes which are separated from the verb in the present form, longest first
+const SEPARABLE_PREFIXES: [&str; 25] = [
+    "zusammen", "zurück", "heraus", "herein", "hinaus", "hinein", "vorbei", "weiter", "fest",
+    "fort", "nach", "auf", "aus", "bei", "ein", "her", "hin", "los", "mit", "vor", "weg", "ab",
+    "an", "um", "zu",
+];
+
+/// Verbs which look like they have a separable prefix but do not
+const NOT_SEPARABLE: [&str; 8] = [
+    "angeln",
+    "antworten",
+    "einigen",
+    "hindern",
+    "umarmen",
+    "umfassen",
+    "umgeben",
+    "abonnieren",
+];
+
+/// Infinitive of the verb split into reflexive pronoun, separable prefix and the verb
+/// itself: `sich aufregen`, `an|rufen` for an explicit prefix
+#[derive(Debug)]
+pub struct VerbParts {
+    /// Reflexive verb: sich freuen
+    pub reflexive: bool,
+    /// Separable prefix: an in anrufen
+    pub prefix: Option<String>,
+    /// Verb without the reflexive pronoun and the prefix
+    pub infinitive: String,
+}
+
+impl VerbParts {
+    /// The stored present form tells apart the verbs with ambiguous prefixes:
+    /// `setzt über` for übersetzen (to ferry) and `übersetzt` for übersetzen (to translate)
+    pub fn parse(word: &str, present_third: &str) -> Result<Self, String> {
+        let word = word.trim();
+        if AnswerSpec::parse(word).alternatives().len() > 1 || word.contains('(') {
+            // several spellings or optional parts: nothing to split
+            return Ok(Self {
+                reflexive: false,
+                prefix: None,
+                infinitive: word.to_owned(),
+            });
+        }
+        let (reflexive, word) = match word.strip_prefix("sich ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, word),
+        };
+        if let Some((prefix, infinitive)) = word.split_once('|') {
+            return Ok(Self {
+                reflexive,
+                prefix: Some(prefix.to_owned()),
+                infinitive: infinitive.to_owned(),
+            });
+        }
+        let stored = Self::stored_tokens(present_third);
+        let prefix = match stored.as_slice() {
+            [_, prefix] if word.starts_with(prefix) => Some(*prefix),
+            [] | [_] => Self::find_prefix(word)
+                .filter(|prefix| !stored.first().is_some_and(|s| s.starts_with(prefix))),
+            _ => {
+                return Err(fill(
+                    Msg::PresentFormMismatch,
+                    &[
+                        &format!("{:?}", present_third.trim()),
+                        &format!("{:?}", word),
+                    ],
+                ))
+            }
+        };
+        Ok(Self {
+            reflexive,
+            infinitive: word[prefix.map(str::len).unwrap_or_default()..].to_owned(),
+            prefix: prefix.map(str::to_owned),
+        })
+    }
+
+    fn find_prefix(word: &str) -> Option<&'static str> {
+        if NOT_SEPARABLE.contains(&word) {
+            return None;
+        }
+        SEPARABLE_PREFIXES.iter().copied().find(|prefix| {
+            // the rest must be a verb itself: zucken is not zu-cken
+            word.strip_prefix(prefix).is_some_and(|rest| {
+                let stem = rest
+                    .strip_suffix("en")
+                    .or_else(|| rest.strip_suffix('n'))
+                    .unwrap_or_default();
+                stem.contains(['a', 'e', 'i', 'o', 'u', 'ä', 'ö', 'ü'])
+            })
+        })
+    }
+
+    /// Words of the stored finite form without the reflexive pronoun and the gap
+    fn stored_tokens(form: &str) -> Vec<&str> {
+        form.split_whitespace()
+            .filter(|s| *s != "sich" && *s != "...")
+            .collect()
+    }
+
+    /// Bring every alternative of the stored finite form to the full one:
+    /// `ruft ... an` - `ruft an`, `freut` - `freut sich`
+    pub fn finite_form(&self, stored: &str) -> Result<AnswerSpec, String> {
+        let alternatives = AnswerSpec::parse(stored)
+            .alternatives()
+            .iter()
+            .map(|alt| self.finite_alternative(alt))
+            .collect::<Result<_, _>>()?;
+        Ok(AnswerSpec::from_alternatives(alternatives))
+    }
+
+    fn finite_alternative(&self, stored: &str) -> Result<String, String> {
+        let tokens = Self::stored_tokens(stored);
+        let base = match (tokens.as_slice(), &self.prefix) {
+            ([base], _) => *base,
+            ([base, prefix], Some(p)) if prefix == p => *base,
+            _ => {
+                return Err(fill(
+                    Msg::VerbFormMismatch,
+                    &[
+                        &format!("{:?}", stored.trim()),
+                        &format!("{:?}", self.to_string()),
+                    ],
+                ))
+            }
+        };
+        Ok(self.with_base(base, "sich"))
+    }
+
+    /// Finite form with the pronoun and the prefix in place: `regt sich auf`
+    pub fn with_base(&self, base: &str, pronoun: &str) -> String {
+        let mut form = base.to_owned();
+        if self.reflexive {
+            form = form + " " + pronoun;
+        }
+        if let Some(prefix) = &self.prefix {
+            form = form + " " + prefix;
+        }
+        form
+    }
+}
+
+impl Display for VerbParts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.reflexive {
+            write!(f, "sich ")?;
+        }
+        write!(
+            f,
+            "{}{}",
+            self.prefix.as_deref().unwrap_or_default(),
+            self.infinitive
+        )
+    }
+}
+
+/// Verb with its forms
 #[derive(Debug)]
 pub struct Verb {
+    /// Fields shared by all words
     pub common: WordCommon,
-    pub praeteritum: String,
-    pub perfect: String,
+    /// Reflexive pronoun and separable prefix of the verb
+    pub parts: VerbParts,
+    /// Preposition and case governed by the verb
+    pub government: Option<Government>,
+    /// Präteritum, third person singular
+    pub praeteritum: AnswerSpec,
+    /// Participle II
+    pub perfect: AnswerSpec,
+    /// Auxiliary verb of the perfect
     pub perfect_verb: Option<PerfectVerb>,
-    pub present_third: String,
+    /// Present tense, third person singular
+    pub present_third: AnswerSpec,
 }
 
 impl Word for Verb {
-    fn pos_str(&self) -> &'static str {
-        "verb"
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        let present_third = row.take(Column::PresentThird).unwrap_or_default();
+        let praeteritum = row.take(Column::Praeteritum).unwrap_or_default();
+        let parts = VerbParts::parse(row.get(Column::Word), &present_third)?;
+        let perfect_verb = PerfectVerb::from_option(row.take(Column::PerfectVerb))?;
+        let government = Government::from_option(row.take(Column::Government))?;
+        let mut common = WordCommon::new(row, db)?;
+        common.word = parts.to_string();
+        common.answers = AnswerSpec::parse(&common.word);
+        Ok(Self {
+            perfect_verb,
+            government,
+            common: common.with_default_id(PartOfSpeech::Verb, None),
+            praeteritum: parts.finite_form(&praeteritum)?,
+            perfect: AnswerSpec::parse(&row.take(Column::Perfect).unwrap_or_default()),
+            present_third: parts.finite_form(&present_third)?,
+            parts,
+        })
     }
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            common: WordCommon::new(map, db),
-            praeteritum: map.remove(&PRAETERITUM_IDX).unwrap_or_default(),
-            perfect: map.remove(&PERFECT_IDX).unwrap_or_default(),
-            perfect_verb: PerfectVerb::from_option(map.remove(&PERFECT_VERB_IDX)),
-            present_third: map.remove(&PRESENT_THIRD_IDX).unwrap_or_default(),
-        }
+    fn translation(&self, lang: Language) -> &str {
+        self.common.translation(lang)
+    }
+
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.common.get_translation_answers(lang)
     }
 
-    fn translation(&self) -> &str {
-        self.common.translation()
+    fn get_answers(&self) -> &AnswerSpec {
+        self.common.get_answers()
     }
 
     fn get_word(&self) -> &str {
         self.common.get_word()
     }
 
+    fn get_id(&self) -> &str {
+        self.common.get_id()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.common.id = id;
+    }
+
     fn get_help(&self) -> &str {
         self.common.get_help()
     }
 
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        self.common.get_extra_columns()
+    }
+
     fn get_group_id(&self) -> usize {
         self.common.get_group_id()
     }
@@ -335,14 +827,14 @@ impl Word for Verb {
         PartOfSpeech::Verb
     }
 
-    fn get_verb_praeteritum(&self) -> Option<&str> {
+    fn get_verb_praeteritum(&self) -> Option<&AnswerSpec> {
         if self.praeteritum.is_empty() {
             return None;
         }
         Some(&self.praeteritum)
     }
 
-    fn get_verb_present_third(&self) -> Option<&str> {
+    fn get_verb_present_third(&self) -> Option<&AnswerSpec> {
         if self.present_third.is_empty() {
             return None;
         }
@@ -353,7 +845,7 @@ impl Word for Verb {
         self.perfect_verb.as_ref()
     }
 
-    fn get_verb_perfect(&self) -> Option<&str> {
+    fn get_verb_perfect(&self) -> Option<&AnswerSpec> {
         if self.perfect.is_empty() {
             return None;
         }
@@ -362,41 +854,202 @@ impl Word for Verb {
 
     fn get_verb_perfect_full(&self) -> Option<String> {
         Some(format!(
-            "{} {}",
+            "{} {}{}",
             self.get_verb_perfect_verb()?,
+            if self.parts.reflexive { "sich " } else { "" },
             self.get_verb_perfect()?
         ))
     }
+
+    fn get_verb_conjugation(&self, tense: Tense, person: Person) -> Option<String> {
+        let infinitive = &self.parts.infinitive;
+        if !infinitive.ends_with('n') || infinitive.contains(char::is_whitespace) {
+            return None;
+        }
+        // the stored forms start with the conjugated verb itself: ruft an - ruft
+        let form = conjugate(
+            infinitive,
+            self.present_third.primary().split_whitespace().next(),
+            self.praeteritum.primary().split_whitespace().next(),
+            tense,
+            person,
+        )?;
+        Some(self.parts.with_base(&form, person.reflexive_pronoun()))
+    }
+
+    fn get_verb_parts(&self) -> Option<&VerbParts> {
+        Some(&self.parts)
+    }
+
+    fn get_government(&self) -> Option<&Government> {
+        self.government.as_ref()
+    }
+
+    fn inherit_verb_forms(&mut self, base: &dyn Word) {
+        let with_prefix = |form: &AnswerSpec| {
+            form.map(|f| {
+                let base = f.split_whitespace().next().unwrap_or_default();
+                self.parts.with_base(base, "sich")
+            })
+        };
+        if self.present_third.is_empty() {
+            if let Some(form) = base.get_verb_present_third() {
+                self.present_third = with_prefix(form);
+            }
+        }
+        if self.praeteritum.is_empty() {
+            if let Some(form) = base.get_verb_praeteritum() {
+                self.praeteritum = with_prefix(form);
+            }
+        }
+        if let (true, Some(prefix), Some(perfect)) = (
+            self.perfect.is_empty(),
+            &self.parts.prefix,
+            base.get_verb_perfect(),
+        ) {
+            self.perfect = perfect.map(|p| format!("{}{}", prefix, p));
+            if self.perfect_verb.is_none() {
+                self.perfect_verb = base.get_verb_perfect_verb().copied();
+            }
+        }
+    }
+}
+
+/// Regular comparative of the adjective: klein - kleiner, dunkel - dunkler, teuer - teurer
+fn regular_comparative(positive: &str) -> String {
+    if let Some(stem) = positive.strip_suffix("el") {
+        format!("{}ler", stem)
+    } else if positive.ends_with("auer") || positive.ends_with("euer") {
+        format!("{}rer", positive.strip_suffix("er").unwrap())
+    } else if positive.ends_with('e') {
+        format!("{}r", positive)
+    } else {
+        format!("{}er", positive)
+    }
+}
+
+/// Regular superlative of the adjective: klein - am kleinsten, breit - am breitesten
+fn regular_superlative(positive: &str) -> String {
+    // suffixes -isch and -end take -sten: am typischsten, but am frischesten
+    let isch_suffix = positive
+        .strip_suffix("isch")
+        .is_some_and(|stem| stem.contains(['a', 'e', 'i', 'o', 'u', 'ä', 'ö', 'ü', 'y']));
+    let long_suffix = !isch_suffix
+        && !positive.ends_with("end")
+        && ["d", "t", "s", "ß", "x", "z", "sch", "au", "eu", "ei"]
+            .iter()
+            .any(|s| positive.ends_with(s));
+    let suffix = if long_suffix { "esten" } else { "sten" };
+    format!("am {}{}", positive, suffix)
+}
+
+/// Superlative of the adjective taking umlaut: alt - am ältesten, but groß - am größten
+fn umlaut_superlative(positive: &str) -> String {
+    if positive.ends_with('ß') {
+        format!("am {}ten", positive)
+    } else {
+        regular_superlative(positive)
+    }
 }
 
+/// Build the comparison forms from the dictionary cells. Blank cells are derived by
+/// the regular rules, `¨` in the comparative cell adds umlaut (alt - älter - am ältesten)
+/// and `-` marks an adjective without comparison
+pub fn parse_comparison(
+    positive: &str,
+    comparative: &str,
+    superlative: &str,
+) -> Result<Option<(String, String)>, String> {
+    let positive = positive.trim();
+    let comparative = comparative.trim();
+    let superlative = superlative.trim();
+    // several spellings or a phrase cannot be derived
+    let derivable = !positive.contains(char::is_whitespace)
+        && AnswerSpec::parse(positive).primary() == positive;
+    if comparative == "-" || (comparative.is_empty() && !derivable) {
+        return Ok(None);
+    }
+    let umlaut_positive = add_umlaut(positive);
+    let comparative = match comparative {
+        "" => regular_comparative(positive),
+        "¨" => regular_comparative(&umlaut_positive),
+        c => c.to_owned(),
+    };
+    let superlative = match superlative
+        .strip_prefix("am ")
+        .unwrap_or(superlative)
+        .trim()
+    {
+        "" if comparative == regular_comparative(positive) => regular_superlative(positive),
+        "" if comparative == regular_comparative(&umlaut_positive) => {
+            umlaut_superlative(&umlaut_positive)
+        }
+        "" => {
+            return Err(fill(
+                Msg::ComparativeNeedsSuperlative,
+                &[&format!("{:?}", comparative)],
+            ))
+        }
+        s => format!("am {}", s),
+    };
+    Ok(Some((comparative, superlative)))
+}
+
+/// Adjective with its comparison forms
 #[derive(Debug)]
 pub struct Adjective {
+    /// Fields shared by all words
     pub common: WordCommon,
+    /// Comparative and superlative, `None` if the adjective is not compared
+    pub comparison: Option<(String, String)>,
+    /// Preposition and case governed by the adjective
+    pub government: Option<Government>,
 }
 
 impl Word for Adjective {
-    fn pos_str(&self) -> &'static str {
-        "adj"
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        let comparative = row.take(Column::Comparative).unwrap_or_default();
+        let superlative = row.take(Column::Superlative).unwrap_or_default();
+        let comparison = parse_comparison(row.get(Column::Word), &comparative, &superlative)?;
+        Ok(Self {
+            comparison,
+            government: Government::from_option(row.take(Column::Government))?,
+            common: WordCommon::new(row, db)?.with_default_id(PartOfSpeech::Adjective, None),
+        })
     }
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            common: WordCommon::new(map, db),
-        }
+    fn translation(&self, lang: Language) -> &str {
+        self.common.translation(lang)
     }
 
-    fn translation(&self) -> &str {
-        self.common.translation()
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.common.get_translation_answers(lang)
+    }
+
+    fn get_answers(&self) -> &AnswerSpec {
+        self.common.get_answers()
     }
 
     fn get_word(&self) -> &str {
         self.common.get_word()
     }
 
+    fn get_id(&self) -> &str {
+        self.common.get_id()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.common.id = id;
+    }
+
     fn get_help(&self) -> &str {
         self.common.get_help()
     }
 
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        self.common.get_extra_columns()
+    }
+
     fn get_group_id(&self) -> usize {
         self.common.get_group_id()
     }
@@ -404,36 +1057,66 @@ impl Word for Adjective {
     fn get_pos(&self) -> PartOfSpeech {
         PartOfSpeech::Adjective
     }
+
+    fn get_comparative(&self) -> Option<&str> {
+        self.comparison.as_ref().map(|(c, _)| c.as_str())
+    }
+
+    fn get_superlative(&self) -> Option<&str> {
+        self.comparison.as_ref().map(|(_, s)| s.as_str())
+    }
+
+    fn get_government(&self) -> Option<&Government> {
+        self.government.as_ref()
+    }
 }
 
+/// Adverb
 #[derive(Debug)]
 pub struct Adverb {
+    /// Fields shared by all words
     pub common: WordCommon,
 }
 
 impl Word for Adverb {
-    fn pos_str(&self) -> &'static str {
-        "adv"
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        Ok(Self {
+            common: WordCommon::new(row, db)?.with_default_id(PartOfSpeech::Adverb, None),
+        })
     }
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            common: WordCommon::new(map, db),
-        }
+    fn translation(&self, lang: Language) -> &str {
+        self.common.translation(lang)
     }
 
-    fn translation(&self) -> &str {
-        self.common.translation()
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.common.get_translation_answers(lang)
+    }
+
+    fn get_answers(&self) -> &AnswerSpec {
+        self.common.get_answers()
     }
 
     fn get_word(&self) -> &str {
         self.common.get_word()
     }
 
+    fn get_id(&self) -> &str {
+        self.common.get_id()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.common.id = id;
+    }
+
     fn get_help(&self) -> &str {
         self.common.get_help()
     }
 
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        self.common.get_extra_columns()
+    }
+
     fn get_group_id(&self) -> usize {
         self.common.get_group_id()
     }
@@ -443,34 +1126,151 @@ impl Word for Adverb {
     }
 }
 
+/// Case governed by a preposition
+#[derive(Debug, EnumIter, PartialEq, Eq, Clone, Copy)]
+pub enum PrepositionCase {
+    /// Akkusativ: für, durch
+    Akkusativ,
+    /// Dativ: mit, bei
+    Dativ,
+    /// Genitiv: wegen, trotz
+    Genitiv,
+    /// Akkusativ for direction, Dativ for location
+    Wechsel,
+}
+
+impl PrepositionCase {
+    /// Case by the cell: `Akk`, `Dat`, `Gen`, `Wechsel`, `None` for an empty cell
+    pub fn from(s: &str) -> Result<Option<Self>, String> {
+        Ok(Some(match s.trim().to_lowercase().as_str() {
+            "akk" | "akkusativ" => PrepositionCase::Akkusativ,
+            "dat" | "dativ" => PrepositionCase::Dativ,
+            "gen" | "genitiv" => PrepositionCase::Genitiv,
+            "wechsel" | "akk/dat" | "dat/akk" => PrepositionCase::Wechsel,
+            "" => {
+                return Ok(None);
+            }
+            _ => {
+                return Err(fill(Msg::UnknownPrepositionCase, &[&format!("{:?}", s)]));
+            }
+        }))
+    }
+
+    /// Case as shown in the options of the case exercise
+    pub fn answer_bullet_str(&self) -> String {
+        match self {
+            Self::Wechsel => text(Msg::TwoWayPreposition).to_owned(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for PrepositionCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Akkusativ => "Akkusativ",
+            Self::Dativ => "Dativ",
+            Self::Genitiv => "Genitiv",
+            Self::Wechsel => "Akkusativ/Dativ",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Preposition and case required by a verb or adjective: warten auf + Akk, helfen + Dat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Government {
+    /// Preposition, `None` for the object without preposition: helfen + Dat
+    pub preposition: Option<String>,
+    /// Governed case
+    pub case: Case,
+}
+
+impl Government {
+    /// Parse `auf + Akk`, `auf Akk` or `Dat`
+    pub fn parse(s: &str) -> Result<Option<Self>, String> {
+        let s = s.replace('+', " ");
+        let mut tokens: Vec<_> = s.split_whitespace().collect();
+        let case = match tokens.pop() {
+            None => return Ok(None),
+            Some(case) => Case::parse(case)
+                .ok_or_else(|| fill(Msg::UnknownGovernmentCase, &[&format!("{:?}", case)]))?,
+        };
+        let preposition = match tokens.as_slice() {
+            [] => None,
+            [prep] => Some(prep.to_lowercase()),
+            _ => return Err(fill(Msg::InvalidGovernment, &[&format!("{:?}", s.trim())])),
+        };
+        Ok(Some(Self { preposition, case }))
+    }
+
+    /// Government of the cell if the column is present
+    pub fn from_option(s: Option<String>) -> Result<Option<Self>, String> {
+        match s {
+            Some(s) => Self::parse(&s),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Display for Government {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.preposition {
+            Some(prep) => write!(f, "{} + {}", prep, self.case.short_name()),
+            None => write!(f, "+ {}", self.case.short_name()),
+        }
+    }
+}
+
+/// Preposition with the governed case
 #[derive(Debug)]
 pub struct Preposition {
+    /// Fields shared by all words
     pub common: WordCommon,
+    /// Case governed by the preposition, `None` if not in the dictionary
+    pub case: Option<PrepositionCase>,
 }
 
 impl Word for Preposition {
-    fn pos_str(&self) -> &'static str {
-        "preposition"
+    fn new(row: &mut WordRow, db: &mut Database) -> Result<Self, String> {
+        Ok(Self {
+            case: PrepositionCase::from(&row.take(Column::Case).unwrap_or_default())?,
+            common: WordCommon::new(row, db)?.with_default_id(PartOfSpeech::Preposition, None),
+        })
     }
 
-    fn new(map: &mut HashMap<usize, String>, db: &mut Database) -> Self {
-        Self {
-            common: WordCommon::new(map, db),
-        }
+    fn translation(&self, lang: Language) -> &str {
+        self.common.translation(lang)
     }
 
-    fn translation(&self) -> &str {
-        self.common.translation()
+    fn get_translation_answers(&self, lang: Language) -> Option<&AnswerSpec> {
+        self.common.get_translation_answers(lang)
+    }
+
+    fn get_answers(&self) -> &AnswerSpec {
+        self.common.get_answers()
     }
 
     fn get_word(&self) -> &str {
         self.common.get_word()
     }
 
+    fn get_id(&self) -> &str {
+        self.common.get_id()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.common.id = id;
+    }
+
     fn get_help(&self) -> &str {
         self.common.get_help()
     }
 
+    fn get_extra_columns(&self) -> Vec<(&str, &str)> {
+        self.common.get_extra_columns()
+    }
+
     fn get_group_id(&self) -> usize {
         self.common.get_group_id()
     }
@@ -478,13 +1278,22 @@ impl Word for Preposition {
     fn get_pos(&self) -> PartOfSpeech {
         PartOfSpeech::Preposition
     }
+
+    fn get_preposition_case(&self) -> Option<PrepositionCase> {
+        self.case
+    }
 }
+
+/// Words of the dictionary with the names of their groups
 pub struct Database {
+    /// Names of the groups, the words refer to them by index
     pub groups: Vec<String>,
-    pub words: HashMap<String, Box<dyn Word>>,
+    /// Ordered by identity, so the random choices of a seeded session are repeatable
+    pub words: BTreeMap<String, Box<dyn Word>>,
 }
 
 impl Database {
+    /// Index of the group by its name, a new group is added
     pub fn get_group_id(&mut self, name: &str) -> usize {
         match self.groups.iter().position(|g| g == name) {
             None => {
@@ -494,4 +1303,198 @@ impl Database {
             Some(i) => i,
         }
     }
+
+    /// Remove the words without translation to the language, return the number of removed words
+    pub fn retain_translated(&mut self, lang: Language) -> usize {
+        let count = self.words.len();
+        self.words
+            .retain(|_, w| !w.translation(lang).trim().is_empty());
+        count - self.words.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::word_row;
+
+    const VERB: [&str; 6] = [
+        "Word",
+        "PoS",
+        "Translation",
+        "Group",
+        "3rd single",
+        "Praeteritum",
+    ];
+
+    fn verb(word: &str, third: &str, past: &str) -> Verb {
+        let mut row = word_row(&VERB, &[word, "v", "-", "1", third, past]);
+        let mut db = Database {
+            groups: vec![],
+            words: BTreeMap::new(),
+        };
+        Verb::new(&mut row, &mut db).unwrap()
+    }
+
+    #[test]
+    fn separable_and_reflexive_verbs_are_conjugated() {
+        let anrufen = verb("anrufen", "ruft an", "rief an");
+        assert_eq!(
+            anrufen
+                .get_verb_conjugation(Tense::Present, Person::Du)
+                .as_deref(),
+            Some("rufst an")
+        );
+        assert_eq!(
+            anrufen
+                .get_verb_conjugation(Tense::Praeteritum, Person::Wir)
+                .as_deref(),
+            Some("riefen an")
+        );
+        let freuen = verb("sich freuen", "freut sich", "freute sich");
+        assert_eq!(
+            freuen
+                .get_verb_conjugation(Tense::Present, Person::Ich)
+                .as_deref(),
+            Some("freue mich")
+        );
+    }
+
+    #[test]
+    fn inseparable_prefix_is_kept() {
+        let verstehen = verb("verstehen", "versteht", "verstand");
+        assert_eq!(
+            verstehen
+                .get_verb_conjugation(Tense::Praeteritum, Person::Du)
+                .as_deref(),
+            Some("verstandest")
+        );
+        let bekommen = verb("bekommen", "bekommt", "");
+        assert_eq!(
+            bekommen
+                .get_verb_conjugation(Tense::Present, Person::Ihr)
+                .as_deref(),
+            Some("bekommt")
+        );
+        assert_eq!(
+            bekommen.get_verb_conjugation(Tense::Praeteritum, Person::Ich),
+            None
+        );
+    }
+
+    #[test]
+    fn verb_without_stored_forms_is_not_conjugated() {
+        let singen = verb("singen", "", "");
+        assert_eq!(
+            singen.get_verb_conjugation(Tense::Present, Person::Ich),
+            None
+        );
+        assert_eq!(
+            singen.get_verb_conjugation(Tense::Praeteritum, Person::Ich),
+            None
+        );
+    }
+
+    #[test]
+    fn capitalize_empty_noun() {
+        assert_eq!(capitalize_noun("übung"), "Übung");
+        assert_eq!(capitalize_noun(""), "");
+    }
+
+    #[test]
+    fn plural_shorthands() {
+        assert_eq!(parse_plural("Hund", "-e").as_deref(), Some("Hunde"));
+        assert_eq!(parse_plural("haus", "¨-er").as_deref(), Some("Häuser"));
+        assert_eq!(parse_plural("Haus", "¨er").as_deref(), Some("Häuser"));
+        assert_eq!(parse_plural("Baum", "¨e").as_deref(), Some("Bäume"));
+        assert_eq!(parse_plural("Mutter", "¨").as_deref(), Some("Mütter"));
+        assert_eq!(parse_plural("Zimmer", "-").as_deref(), Some("Zimmer"));
+        assert_eq!(
+            parse_plural("Lehrerin", "-nen").as_deref(),
+            Some("Lehrerinnen")
+        );
+    }
+
+    #[test]
+    fn plural_full_forms() {
+        assert_eq!(parse_plural("Museum", "Museen").as_deref(), Some("Museen"));
+        assert_eq!(parse_plural("Apfel", "die äpfel").as_deref(), Some("Äpfel"));
+        assert_eq!(
+            parse_plural("Kaufmann", "Kaufleute").as_deref(),
+            Some("Kaufleute")
+        );
+    }
+
+    #[test]
+    fn unrecognized_plurals() {
+        assert_eq!(parse_plural("Hund", ""), None);
+        assert_eq!(parse_plural("", "-e"), None);
+        assert_eq!(parse_plural("Hund", "e"), None);
+        assert_eq!(parse_plural("Hund", "-E"), None);
+        assert_eq!(parse_plural("Hund", "-e?"), None);
+        assert_eq!(parse_plural("Tisch", "¨e"), None);
+        assert_eq!(parse_plural("Hund", "kein Plural"), None);
+    }
+
+    fn comparison(positive: &str, comparative: &str, superlative: &str) -> (String, String) {
+        parse_comparison(positive, comparative, superlative)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn regular_comparison() {
+        let forms = |c: &str, s: &str| (c.to_owned(), s.to_owned());
+        assert_eq!(
+            comparison("klein", "", ""),
+            forms("kleiner", "am kleinsten")
+        );
+        assert_eq!(
+            comparison("breit", "", ""),
+            forms("breiter", "am breitesten")
+        );
+        assert_eq!(comparison("süß", "", ""), forms("süßer", "am süßesten"));
+        assert_eq!(
+            comparison("typisch", "", ""),
+            forms("typischer", "am typischsten")
+        );
+        assert_eq!(
+            comparison("frisch", "", ""),
+            forms("frischer", "am frischesten")
+        );
+        assert_eq!(
+            comparison("dunkel", "", ""),
+            forms("dunkler", "am dunkelsten")
+        );
+        assert_eq!(comparison("teuer", "", ""), forms("teurer", "am teuersten"));
+        assert_eq!(comparison("leise", "", ""), forms("leiser", "am leisesten"));
+    }
+
+    #[test]
+    fn umlaut_comparison() {
+        let forms = |c: &str, s: &str| (c.to_owned(), s.to_owned());
+        assert_eq!(comparison("alt", "¨", ""), forms("älter", "am ältesten"));
+        assert_eq!(comparison("groß", "¨", ""), forms("größer", "am größten"));
+        assert_eq!(
+            comparison("groß", "größer", ""),
+            forms("größer", "am größten")
+        );
+        assert_eq!(comparison("jung", "¨", ""), forms("jünger", "am jüngsten"));
+    }
+
+    #[test]
+    fn irregular_comparison() {
+        let forms = |c: &str, s: &str| (c.to_owned(), s.to_owned());
+        assert_eq!(
+            comparison("gut", "besser", "am besten"),
+            forms("besser", "am besten")
+        );
+        assert_eq!(
+            comparison("hoch", "höher", "höchsten"),
+            forms("höher", "am höchsten")
+        );
+        assert!(parse_comparison("gut", "besser", "").is_err());
+        assert_eq!(parse_comparison("tot", "-", "").unwrap(), None);
+        assert_eq!(parse_comparison("zu Hause", "", "").unwrap(), None);
+    }
 }